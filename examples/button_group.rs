@@ -1,4 +1,6 @@
 use juquad::draw::draw_rect_lines;
+use juquad::fonts;
+use juquad::fonts::{FontId, DEFAULT_FONT};
 use juquad::widgets::anchor::{Anchor, Horizontal};
 use juquad::widgets::button::Button;
 use juquad::widgets::button_group::{Direction, LabelGroup};
@@ -7,7 +9,7 @@ use juquad::widgets::{Style, WHITE_BLUE_BACKGROUND};
 use macroquad::color::BLACK;
 use macroquad::math::Vec2;
 use macroquad::prelude::{clear_background, next_frame, screen_height, screen_width, Rect, RED};
-use macroquad::text::{draw_text, load_ttf_font_from_bytes, Font};
+use macroquad::text::draw_text;
 
 const STYLE: Style = Style::new();
 
@@ -27,11 +29,11 @@ async fn main() {
     let mut font_size: f32 = 16.0;
     // let font_bytes = include_bytes!("../assets/Roboto-Regular.ttf");
     let font_bytes = include_bytes!("../assets/Saira-Regular.ttf");
-    let font = load_ttf_font_from_bytes(font_bytes).unwrap();
+    let font = fonts::load_ttf_bytes(font_bytes).unwrap();
     let mut custom_font = false;
     let mut alignment = Horizontal::Center;
 
-    let mut buttons = create_button_group(None, font_size, alignment);
+    let mut buttons = create_button_group(DEFAULT_FONT, font_size, alignment);
 
     let mut show_extra_buttons = false;
     let mut show_borders = false;
@@ -101,20 +103,16 @@ async fn main() {
             draw_rect_lines(text_border(&buttons.exit.text_rect), 2.0, RED);
         }
         if update_buttons {
-            let font_option = if custom_font {
-                Some(font.clone())
-            } else {
-                None
-            };
-            buttons = create_button_group(font_option, font_size, alignment);
+            let font_id = if custom_font { font } else { DEFAULT_FONT };
+            buttons = create_button_group(font_id, font_size, alignment);
         }
 
         next_frame().await
     }
 }
 
-fn create_button_group(font: Option<Font>, font_size: f32, alignment: Horizontal) -> Buttons {
-    let _pad = if font.is_some() {
+fn create_button_group(font: FontId, font_size: f32, alignment: Horizontal) -> Buttons {
+    let _pad = if font != DEFAULT_FONT {
         Vec2::new(font_size * 2.0, font_size * 0.65)
     } else {
         Vec2::new(font_size, font_size * 0.25)
@@ -1,18 +1,24 @@
 use juquad::draw::to_rect;
 use juquad::elm::button::Button;
+use juquad::elm::checkbox::Checkbox;
 use juquad::elm::container::Container;
+use juquad::elm::radio_group::RadioGroup;
 use juquad::elm::slider::Slider;
 use juquad::elm::style::Style;
 use juquad::elm::text::Text;
 use juquad::elm::widget::{compute_layout, RenderableWidget};
+use juquad::fonts;
+use juquad::lazy::{Border, Pad};
 use juquad::widgets::anchor::{Horizontal, Layout, Spot, Vertical};
 use juquad::SizeInPixels2d;
 use macroquad::miniquad::date::now;
 use macroquad::prelude::{
-    clear_background, is_key_pressed, is_mouse_button_pressed, load_ttf_font_from_bytes,
-    mouse_position, next_frame, screen_height, screen_width, vec2, KeyCode, MouseButton,
+    clear_background, is_key_pressed, is_mouse_button_pressed, mouse_position, next_frame,
+    screen_height, screen_width, vec2, KeyCode, MouseButton,
 };
 
+const FONT_SIZES: [f32; 3] = [12.0, 16.0, 20.0];
+
 #[derive(Copy, Clone)]
 pub enum Message {
     None,
@@ -21,6 +27,8 @@ pub enum Message {
     PadY(f32),
     MarginX(f32),
     MarginY(f32),
+    Borders(bool),
+    FontSize(f32),
 }
 
 #[macroquad::main("juquad elm ui")]
@@ -28,17 +36,17 @@ async fn main() {
     let font_size: f32 = 16.0;
     // let font_bytes = include_bytes!("../assets/Saira-Regular.ttf");
     let font_bytes = include_bytes!("../assets/Roboto-Regular.ttf");
-    let font = Some(load_ttf_font_from_bytes(font_bytes).unwrap());
+    let font = fonts::load_ttf_bytes(font_bytes).unwrap();
     // let pad = Pad::new_symmetric(10.0);
     // let pad = Pad::new(0.0, 0.0);
     // let margin = Pad::new(0.0, 10.0);
-    let mut style = Style {
+    let mut style = Style::from(juquad::lazy::Style {
         font_size,
         // pad,
         // margin,
         font,
         ..Default::default()
-    };
+    });
 
     // unsafe {
     //     DEBUG_WIDGETS = true;
@@ -50,8 +58,12 @@ async fn main() {
         let _start = now();
         let new_screen = vec2(screen_width(), screen_height());
         if new_screen != screen {
+            // the widgets didn't change, so only their sizes and positions are computed again
             screen = new_screen;
-            recalculate_ui = true;
+            let screen_rect = to_rect(vec2(0.0, 0.0), screen);
+            for overflow in compute_layout(&mut *ui, screen_rect, style.layout) {
+                println!("{}", overflow);
+            }
         }
         if recalculate_ui {
             recalculate_ui = false;
@@ -67,16 +79,24 @@ async fn main() {
                 Message::None => {}
                 Message::Exit => break 'main_loop,
                 Message::PadX(new_value) => {
-                    maybe_modify(&mut style.pad.x, new_value, &mut recalculate_ui);
+                    maybe_modify_axis(&mut style.pad, 0, new_value, &mut recalculate_ui);
                 }
                 Message::PadY(new_value) => {
-                    maybe_modify(&mut style.pad.y, new_value, &mut recalculate_ui);
+                    maybe_modify_axis(&mut style.pad, 1, new_value, &mut recalculate_ui);
                 }
                 Message::MarginX(new_value) => {
-                    maybe_modify(&mut style.margin.x, new_value, &mut recalculate_ui);
+                    maybe_modify_axis(&mut style.margin, 0, new_value, &mut recalculate_ui);
                 }
                 Message::MarginY(new_value) => {
-                    maybe_modify(&mut style.margin.y, new_value, &mut recalculate_ui);
+                    maybe_modify_axis(&mut style.margin, 1, new_value, &mut recalculate_ui);
+                }
+                Message::FontSize(new_value) => {
+                    maybe_modify(&mut style.font_size, new_value, &mut recalculate_ui);
+                }
+                Message::Borders(visible) => {
+                    let width = if visible { 1.0 } else { 0.0 };
+                    style.border = Border::new_symmetric(width);
+                    recalculate_ui = true;
                 }
             }
         }
@@ -99,6 +119,12 @@ fn maybe_modify(current: &mut f32, new_value: f32, recalculate_ui: &mut bool) {
     }
 }
 
+fn maybe_modify_axis(pad: &mut Pad, axis: usize, new_value: f32, recalculate_ui: &mut bool) {
+    let mut current = pad.start()[axis];
+    maybe_modify(&mut current, new_value, recalculate_ui);
+    pad.set_axis(axis, current);
+}
+
 fn float_eq(a: f32, b: f32, epsilon: f32) -> bool {
     (a - b).abs() < epsilon
 }
@@ -153,10 +179,9 @@ fn rotate_layout(style: &mut Style) {
 
 fn rebuild_ui(screen: SizeInPixels2d, style: &Style) -> Box<dyn RenderableWidget<Message>> {
     let start = now();
-    let style_horizontal = &Style {
-        layout: Layout::horizontal(Horizontal::Right, Vertical::Center),
-        ..style.clone()
-    };
+    let mut style_horizontal = style.clone();
+    style_horizontal.layout = Layout::horizontal(Horizontal::Right, Vertical::Center);
+    let style_horizontal = &style_horizontal;
 
     let mut ui = Container::new(
         style,
@@ -165,27 +190,41 @@ fn rebuild_ui(screen: SizeInPixels2d, style: &Style) -> Box<dyn RenderableWidget
             Container::new(
                 style_horizontal,
                 vec![
-                    Text::new(style, format!("Pad x: {:0>6.2}", style.pad.x)),
-                    Slider::new(style, 0.0, 100.0, style.pad.x, Message::PadX),
-                    Text::new(style, format!("Pad y: {:0>6.2}", style.pad.y)),
-                    Slider::new(style, 0.0, 100.0, style.pad.y, Message::PadY),
+                    Text::new(style, format!("Pad x: {:0>6.2}", style.pad.left)),
+                    Slider::new(style, 0.0, 100.0, style.pad.left, Message::PadX),
+                    Text::new(style, format!("Pad y: {:0>6.2}", style.pad.top)),
+                    Slider::new(style, 0.0, 100.0, style.pad.top, Message::PadY),
                 ],
             ),
             Container::new(
                 style_horizontal,
                 vec![
-                    Text::new(style, format!("Margin x: {:0>6.2}", style.margin.x)),
-                    Slider::new(style, 0.0, 100.0, style.margin.x, Message::MarginX),
-                    Text::new(style, format!("Margin y: {:0>6.2}", style.margin.y)),
-                    Slider::new(style, 0.0, 100.0, style.margin.y, Message::MarginY),
+                    Text::new(style, format!("Margin x: {:0>6.2}", style.margin.left)),
+                    Slider::new(style, 0.0, 100.0, style.margin.left, Message::MarginX),
+                    Text::new(style, format!("Margin y: {:0>6.2}", style.margin.top)),
+                    Slider::new(style, 0.0, 100.0, style.margin.top, Message::MarginY),
                 ],
             ),
+            RadioGroup::new_options(
+                style_horizontal,
+                FONT_SIZES
+                    .iter()
+                    .map(|size| (format!("{}", size), Message::FontSize(*size)))
+                    .collect(),
+                FONT_SIZES
+                    .iter()
+                    .position(|size| *size == style.font_size)
+                    .unwrap_or(0),
+            ),
+            Checkbox::new_switch(style, "Borders", style.border.top > 0.0, Message::Borders),
             Button::new_text(style, Message::Exit, "Exit"),
         ],
     );
 
     let screen_rect = to_rect(vec2(0.0, 0.0), screen);
-    compute_layout(&mut *ui, screen_rect, style.layout);
+    for overflow in compute_layout(&mut *ui, screen_rect, style.layout) {
+        println!("{}", overflow);
+    }
 
     print_time_since(start, "rebuilt ui in");
     ui
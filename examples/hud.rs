@@ -0,0 +1,53 @@
+use juquad::lazy::Pad;
+use juquad::widgets::anchor::Anchor;
+use juquad::widgets::button::Button;
+use juquad::widgets::hud::{Dock, Hud};
+use juquad::widgets::text::TextRect;
+use juquad::widgets::{Style, Widget};
+use macroquad::prelude::{next_frame, screen_height, screen_width, Rect};
+
+const FONT_SIZE: f32 = 16.0;
+const STYLE: Style = Style::new();
+
+#[macroquad::main("juquad hud")]
+async fn main() {
+    let screen = || Rect::new(0.0, 0.0, screen_width(), screen_height());
+    let mut hud = Hud::new(screen(), Pad::new_symmetric(16.0), 4.0);
+
+    let mut score = TextRect::new("score: 0", Anchor::default(), FONT_SIZE);
+    let mut lives = TextRect::new("lives: 3", Anchor::default(), FONT_SIZE);
+    let mut add_points = Button::new("+10 points", Anchor::default(), FONT_SIZE);
+    let mut exit = Button::new("exit", Anchor::default(), FONT_SIZE);
+    let score_id = hud.add(Dock::TOP_RIGHT, score.size());
+    let lives_id = hud.add(Dock::TOP_RIGHT, lives.size());
+    let points_id = hud.add(Dock::BOTTOM, add_points.size());
+    let exit_id = hud.add(Dock::BOTTOM, exit.size());
+
+    let mut points = 0;
+    loop {
+        if add_points.interact().is_clicked() {
+            points += 10;
+            score = TextRect::new(&format!("score: {}", points), Anchor::default(), FONT_SIZE);
+            hud.set_size(score_id, score.size());
+        }
+        if exit.interact().is_clicked() {
+            break;
+        }
+
+        // only does something when the window is resized or a size changed
+        hud.set_screen(screen());
+        hud.relayout();
+        // applied every frame, as a rebuilt widget starts at its default anchor even if its size
+        // didn't change
+        hud.apply(score_id, &mut score);
+        hud.apply(lives_id, &mut lives);
+        hud.apply(points_id, &mut add_points);
+        hud.apply(exit_id, &mut exit);
+
+        score.render_default(&STYLE.at_rest);
+        lives.render_default(&STYLE.at_rest);
+        add_points.render_default(&STYLE);
+        exit.render_default(&STYLE);
+        next_frame().await
+    }
+}
@@ -1,18 +1,25 @@
-use juquad::widgets::anchor::{Anchor, Horizontal, Layout, Vertical};
+use juquad::widgets::anchor::{Anchor, Horizontal, Layout, RelativeAnchor, Vertical};
 use juquad::widgets::button::Button;
-use juquad::widgets::Style;
-use macroquad::prelude::{next_frame, screen_height, screen_width};
+use juquad::widgets::{Style, Widget};
+use macroquad::prelude::{next_frame, vec2, Vec2};
 
 const FONT_SIZE: f32 = 16.0;
 const STYLE: Style = Style::new();
 
 #[macroquad::main("juquad individual buttons")]
 async fn main() {
-    let anchor = Anchor::center(screen_width() * 0.5, screen_height() * 0.125);
-    let mut button_enable = Button::new("toggle many buttons", anchor, FONT_SIZE);
+    // stays at the same part of the screen if the window is resized
+    let top = RelativeAnchor::new(
+        Horizontal::Center,
+        Vertical::Center,
+        vec2(0.5, 0.125),
+        Vec2::ZERO,
+    );
+    let mut button_enable = Button::new("toggle many buttons", top.current(), FONT_SIZE);
     let mut alignment = Horizontal::Left;
     let mut buttons_enabled = false;
     loop {
+        button_enable.reanchor(top.current());
         let layout = Layout::vertical(Vertical::Bottom, alignment);
         let anchor = Anchor::next_to(button_enable.rect(), layout, 0.0);
         let mut button_1 = Button::new("toggle center", anchor, FONT_SIZE);
@@ -1,4 +1,5 @@
 use juquad::draw::to_rect;
+use juquad::fonts::DEFAULT_FONT;
 use juquad::lazy::button::Button;
 use juquad::lazy::panel::Panel;
 use juquad::lazy::slider::Slider;
@@ -155,19 +156,17 @@ async fn main() {
 
         let slider_values = buttons.pad.interact_t(2);
         let style_pad = &mut style.pad;
-        for i in 0..=1 {
-            let slider_value: f32 = slider_values[i];
-            if !float_eq(slider_value, style_pad[i], 0.01) {
-                style_pad[i] = slider_value;
+        for (i, slider_value) in slider_values.into_iter().enumerate() {
+            if !float_eq(slider_value, style_pad.start()[i], 0.01) {
+                style_pad.set_axis(i, slider_value);
                 recalculate_ui = true;
             }
         }
         let slider_values = buttons.margin.interact_t(2);
         let style_pad = &mut style.margin;
-        for i in 0..=1 {
-            let slider_value: f32 = slider_values[i];
-            if !float_eq(slider_value, style_pad[i], 0.01) {
-                style_pad[i] = slider_value;
+        for (i, slider_value) in slider_values.into_iter().enumerate() {
+            if !float_eq(slider_value, style_pad.start()[i], 0.01) {
+                style_pad.set_axis(i, slider_value);
                 recalculate_ui = true;
             }
         }
@@ -241,30 +240,27 @@ fn rebuild_ui(screen: SizeInPixels2d, style: &Style) -> Buttons {
     let start = now();
 
     let text_style = Style {
-        font: None,
-        pad: Pad::new(style.pad.vec2().x, style.pad.vec2().y * 0.5),
-        margin: Pad::new(style.margin.vec2().x, 0.0),
-        ..*style
+        font: DEFAULT_FONT,
+        pad: Pad::new(style.pad.left, style.pad.top * 0.5),
+        margin: Pad::new(style.margin.left, 0.0),
+        ..style.clone()
     };
     let horizontal_layout = Layout::horizontal(Horizontal::Right, Vertical::Center);
     let button_style = Style {
         layout: horizontal_layout,
         pad: Pad::new_symmetric(0.0),
-        font: style.font.clone(),
-        ..*style
+        ..style.clone()
     };
     let slider_container_style = Style {
         pad: Pad::new_symmetric(0.0),
         margin: Pad::new_symmetric(0.0),
         layout: horizontal_layout,
-        font: style.font.clone(),
-        ..*style
+        ..style.clone()
     };
     let mut buttons = Buttons {
         panel: Panel::leaf(Style {
             size: Size::Grow,
-            font: style.font.clone(),
-            ..*style
+            ..style.clone()
         }),
         some_text: Text::new(&text_style, "Title"),
         toggle_alignment: Button::container(
@@ -286,16 +282,10 @@ fn rebuild_ui(screen: SizeInPixels2d, style: &Style) -> Buttons {
         pad: Panel::container(
             slider_container_style.clone(),
             vec![
-                Box::new(Text::new(
-                    style,
-                    &format!("Pad x: {:.1}", style.pad.vec2().x),
-                )),
-                Box::new(Slider::new(style.clone(), 0.0, 50.0, style.pad.vec2().x)),
-                Box::new(Text::new(
-                    style,
-                    &format!("Pad y: {:.1}", style.pad.vec2().y),
-                )),
-                Box::new(Slider::new(style.clone(), 0.0, 50.0, style.pad.vec2().y)),
+                Box::new(Text::new(style, &format!("Pad x: {:.1}", style.pad.left))),
+                Box::new(Slider::new(style.clone(), 0.0, 50.0, style.pad.left)),
+                Box::new(Text::new(style, &format!("Pad y: {:.1}", style.pad.top))),
+                Box::new(Slider::new(style.clone(), 0.0, 50.0, style.pad.top)),
             ],
         ),
         margin: Panel::container(
@@ -303,14 +293,14 @@ fn rebuild_ui(screen: SizeInPixels2d, style: &Style) -> Buttons {
             vec![
                 Box::new(Text::new(
                     style,
-                    &format!("Margin x: {:.1}", style.margin.vec2().x),
+                    &format!("Margin x: {:.1}", style.margin.left),
                 )),
-                Box::new(Slider::new(style.clone(), 0.0, 50.0, style.margin.vec2().x)),
+                Box::new(Slider::new(style.clone(), 0.0, 50.0, style.margin.left)),
                 Box::new(Text::new(
                     style,
-                    &format!("Margin y: {:.1}", style.margin.vec2().y),
+                    &format!("Margin y: {:.1}", style.margin.top),
                 )),
-                Box::new(Slider::new(style.clone(), 0.0, 50.0, style.margin.vec2().y)),
+                Box::new(Slider::new(style.clone(), 0.0, 50.0, style.margin.top)),
             ],
         ),
         exit: Button::container(button_style, vec![Box::new(Text::new(&text_style, "Exit"))]),
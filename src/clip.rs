@@ -0,0 +1,149 @@
+//! Nested clipping rects, used by containers that show only part of their children, like
+//! [`crate::elm::scroll::Scroll`].
+//!
+//! While interacting, [`crate::widgets::interact`] only hit-tests the part of a widget inside the
+//! current clip, so that scrolled-out widgets can't be clicked. While rendering, the clip is also
+//! applied as a scissor rect.
+
+use macroquad::math::{vec3, Mat4, Rect};
+use macroquad::window::{get_internal_gl, screen_dpi_scale, screen_height, screen_width};
+use std::cell::RefCell;
+
+thread_local! {
+    static CLIPS: RefCell<Vec<Rect>> = const { RefCell::new(Vec::new()) };
+}
+
+/// The intersection of all the clips being applied, if any.
+pub fn current() -> Option<Rect> {
+    CLIPS.with(|clips| clips.borrow().last().copied())
+}
+
+/// The part of `rect` inside the current clip. If there's nothing inside, the returned rect has
+/// no area.
+pub fn clip_rect(rect: Rect) -> Rect {
+    match current() {
+        Some(clip) => intersect(rect, clip),
+        None => rect,
+    }
+}
+
+fn intersect(rect: Rect, clip: Rect) -> Rect {
+    rect.intersect(clip)
+        .unwrap_or(Rect::new(rect.x, rect.y, 0.0, 0.0))
+}
+
+/// Runs `f` with `rect` (intersected with the current clip) as the clip for hit-testing.
+pub fn with_input_clip<R>(rect: Rect, f: impl FnOnce() -> R) -> R {
+    push(rect);
+    let result = f();
+    pop();
+    result
+}
+
+/// Like [`with_input_clip`], but also doesn't draw anything outside the clip.
+pub fn with_render_clip<R>(rect: Rect, f: impl FnOnce() -> R) -> R {
+    push(rect);
+    apply_scissor();
+    let result = f();
+    pop();
+    apply_scissor();
+    result
+}
+
+fn push(rect: Rect) {
+    let clipped = clip_rect(rect);
+    CLIPS.with(|clips| clips.borrow_mut().push(clipped));
+}
+
+fn pop() {
+    CLIPS.with(|clips| clips.borrow_mut().pop());
+}
+
+fn apply_scissor() {
+    let gl = unsafe { get_internal_gl() }.quad_gl;
+    let dpi = screen_dpi_scale();
+    let framebuffer = Rect::new(0.0, 0.0, screen_width() * dpi, screen_height() * dpi);
+    // without a camera viewport, macroquad reports the screen in logical pixels
+    let (x, y, w, h) = gl.get_viewport();
+    let viewport = if (x, y, w, h) == (0, 0, screen_width() as i32, screen_height() as i32) {
+        framebuffer
+    } else {
+        Rect::new(x as f32, y as f32, w as f32, h as f32)
+    };
+    let scissor = current()
+        .map(|clip| scissor_rect(clip, gl.get_projection_matrix(), viewport, framebuffer.h));
+    gl.scissor(scissor);
+}
+
+/// The scissor of `clip` in framebuffer pixels, from the top left corner, after transforming it
+/// with the `projection` of the active camera into its `viewport` (in pixels from the bottom left
+/// corner, like OpenGL). The edges are rounded outwards, so that partially covered pixels are
+/// drawn.
+fn scissor_rect(
+    clip: Rect,
+    projection: Mat4,
+    viewport: Rect,
+    framebuffer_height: f32,
+) -> (i32, i32, i32, i32) {
+    let to_pixels = |x: f32, y: f32| {
+        let ndc = projection.project_point3(vec3(x, y, 0.0));
+        let from_bottom = viewport.y + (ndc.y + 1.0) * 0.5 * viewport.h;
+        (
+            viewport.x + (ndc.x + 1.0) * 0.5 * viewport.w,
+            framebuffer_height - from_bottom,
+        )
+    };
+    let (x0, y0) = to_pixels(clip.left(), clip.top());
+    let (x1, y1) = to_pixels(clip.right(), clip.bottom());
+    let left = x0.min(x1).floor();
+    let top = y0.min(y1).floor();
+    let right = x0.max(x1).ceil();
+    let bottom = y0.max(y1).ceil();
+    (
+        left as i32,
+        top as i32,
+        (right - left) as i32,
+        (bottom - top) as i32,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use macroquad::math::vec2;
+
+    #[test]
+    fn test_nested_input_clips() {
+        let widget = Rect::new(50.0, 50.0, 100.0, 100.0);
+        assert_eq!(clip_rect(widget), widget);
+        with_input_clip(Rect::new(0.0, 0.0, 120.0, 120.0), || {
+            assert_eq!(clip_rect(widget), Rect::new(50.0, 50.0, 70.0, 70.0));
+            with_input_clip(Rect::new(100.0, 0.0, 100.0, 100.0), || {
+                assert_eq!(clip_rect(widget), Rect::new(100.0, 50.0, 20.0, 50.0));
+            });
+            let outside = Rect::new(200.0, 200.0, 10.0, 10.0);
+            assert_eq!(clip_rect(outside).size(), vec2(0.0, 0.0));
+        });
+        assert_eq!(current(), None);
+    }
+
+    #[test]
+    fn test_scissor_rect() {
+        let clip = Rect::new(10.25, 20.5, 30.5, 40.0);
+        let framebuffer = Rect::new(0.0, 0.0, 800.0, 600.0);
+        // the projection without camera with a dpi scale of 2
+        let screen = Mat4::orthographic_rh_gl(0.0, 400.0, 300.0, 0.0, -1.0, 1.0);
+        assert_eq!(
+            scissor_rect(clip, screen, framebuffer, 600.0),
+            (20, 41, 62, 80)
+        );
+
+        // a camera that shows twice as much and is drawn in the bottom half of the framebuffer
+        let zoomed_out = Mat4::orthographic_rh_gl(0.0, 1600.0, 600.0, 0.0, -1.0, 1.0);
+        let bottom_half = Rect::new(0.0, 0.0, 800.0, 300.0);
+        assert_eq!(
+            scissor_rect(clip, zoomed_out, bottom_half, 600.0),
+            (5, 310, 16, 21)
+        );
+    }
+}
@@ -1,12 +1,14 @@
-use crate::draw::{draw_rect, draw_rect_lines};
+use crate::draw::draw_rect;
 use crate::elm::style::Style;
 use crate::elm::text::Text;
 use crate::elm::widget::{
-    Interactable, Renderable, RenderableWidget, Widget, WidgetTrait, Widgets,
+    relocalize_children, Interactable, Localizable, Renderable, RenderableWidget, Widget,
+    WidgetTrait, Widgets,
 };
 use crate::input::input_macroquad::InputMacroquad;
 use crate::input::input_trait::InputTrait;
-use crate::lazy::{Margin, Pad};
+use crate::lazy::dump::{describe, Describe};
+use crate::lazy::{draw_border, Border, LayoutState, Margin, Pad};
 use crate::widgets::{interact, Interaction};
 
 pub type Button<I> = Widget<ButtonBase<I>, I>;
@@ -19,6 +21,12 @@ pub struct ButtonBase<I> {
     pub on_press: I,
 }
 
+impl<I> Describe for ButtonBase<I> {
+    fn kind(&self) -> &'static str {
+        "Button"
+    }
+}
+
 impl<I: Clone + 'static> Button<I> {
     pub fn new<Sty: Into<Style>>(
         style: Sty,
@@ -48,6 +56,7 @@ impl<I: Clone + 'static> Button<I> {
         let mut text_style = button_style.clone();
         button_style.pad = Pad::new_symmetric(0.0);
         text_style.margin = Margin::new_symmetric(0.0);
+        text_style.border = Border::new_symmetric(0.0);
         Self::new_raw(button_style, on_press, vec![Text::new(text_style, text)])
     }
     pub fn new_generic(
@@ -66,6 +75,10 @@ impl<I: Clone + 'static> Button<I> {
         Self {
             pos: Default::default(),
             size: None,
+            content_size: None,
+            layout_state: LayoutState::default(),
+            measure: None,
+            describe: Some(describe),
             style,
             custom,
             children,
@@ -91,6 +104,12 @@ impl<I: Clone + 'static> Interactable<I> for Button<I> {
     }
 }
 
+impl<I> Localizable for Button<I> {
+    fn relocalize(&mut self) -> bool {
+        relocalize_children(&mut self.children)
+    }
+}
+
 impl<I> Renderable for Button<I> {
     fn render_interactive(&self, interaction: Interaction) {
         (self.custom.render_button)(self, interaction)
@@ -100,15 +119,13 @@ impl<I> Renderable for Button<I> {
 fn render_interactive<I>(widget: &Button<I>, _unused: Interaction) {
     let state_style = widget.style().coloring.choose(widget.custom.interaction);
     draw_rect(widget.rect(), state_style.bg_color);
-    draw_rect_lines(
-        widget.rect(),
-        widget.style.border * 2.0,
-        state_style.border_color,
-    );
+    draw_border(widget.rect(), widget.style.border, state_style.border_color);
     // if unsafe { DEBUG_WIDGETS } {
     //     draw_debug_widget(widget);
     // }
     for child in widget.children() {
-        child.render_interactive(widget.custom.interaction);
+        if child.style().visible {
+            child.render_interactive(widget.custom.interaction);
+        }
     }
 }
@@ -0,0 +1,225 @@
+use crate::elm::style::Style;
+use crate::elm::text::Text;
+use crate::elm::widget::{
+    relocalize_children, Interactable, Localizable, Renderable, RenderableWidget, Widget,
+    WidgetTrait, Widgets,
+};
+use crate::input::input_macroquad::InputMacroquad;
+use crate::input::input_trait::InputTrait;
+use crate::lazy::dump::{describe, Describe};
+use crate::lazy::{LayoutState, Margin, Pad};
+use crate::widgets::checkbox::{
+    draw_indicator, indicator_rect, indicator_start, widen_pads, ToggleKind,
+};
+use crate::widgets::{interact, Interaction};
+
+/// A checkbox or a toggle switch, see [`crate::widgets::checkbox`]. The children are the label,
+/// and the indicator goes in the left pad, which is widened to have the indicator with the pad at
+/// each side of it.
+pub type Checkbox<I> = Widget<CheckboxBase<I>, I>;
+pub type RenderCheckbox<I> = fn(widget: &Checkbox<I>, interaction: Interaction);
+/// Makes the message for the new value after a click.
+pub type OnToggle<I> = fn(bool) -> I;
+
+pub struct CheckboxBase<I> {
+    pub checked: bool,
+    pub kind: ToggleKind,
+    pub interaction: Interaction,
+    pub input: Box<dyn InputTrait>,
+    pub render_checkbox: RenderCheckbox<I>,
+    pub on_toggle: OnToggle<I>,
+}
+
+impl<I> Describe for CheckboxBase<I> {
+    fn kind(&self) -> &'static str {
+        self.kind.name()
+    }
+}
+
+impl<I: Clone + 'static> Checkbox<I> {
+    pub fn new<Sty: Into<Style>>(
+        style: Sty,
+        text: &str,
+        checked: bool,
+        on_toggle: OnToggle<I>,
+    ) -> Box<dyn RenderableWidget<I>> {
+        Box::new(Self::new_raw(style, text, checked, on_toggle))
+    }
+    pub fn new_raw<Sty: Into<Style>>(
+        style: Sty,
+        text: &str,
+        checked: bool,
+        on_toggle: OnToggle<I>,
+    ) -> Self {
+        Self::new_text_raw(style.into(), text, checked, ToggleKind::Checkbox, on_toggle)
+    }
+    pub fn new_switch<Sty: Into<Style>>(
+        style: Sty,
+        text: &str,
+        checked: bool,
+        on_toggle: OnToggle<I>,
+    ) -> Box<dyn RenderableWidget<I>> {
+        Box::new(Self::new_switch_raw(style, text, checked, on_toggle))
+    }
+    pub fn new_switch_raw<Sty: Into<Style>>(
+        style: Sty,
+        text: &str,
+        checked: bool,
+        on_toggle: OnToggle<I>,
+    ) -> Self {
+        Self::new_text_raw(style.into(), text, checked, ToggleKind::Switch, on_toggle)
+    }
+    fn new_text_raw(
+        style: Style,
+        text: &str,
+        checked: bool,
+        kind: ToggleKind,
+        on_toggle: OnToggle<I>,
+    ) -> Self {
+        let mut text_style = style.clone();
+        // the checkbox pads the label
+        text_style.margin = Margin::new_symmetric(0.0);
+        text_style.pad = Pad::new_symmetric(0.0);
+        let base = &mut text_style.base;
+        for breakpoint in &mut base.breakpoints {
+            breakpoint.variant.pad = base.pad;
+        }
+        Self::new_generic(
+            style,
+            checked,
+            kind,
+            Box::new(InputMacroquad),
+            render_checkbox,
+            on_toggle,
+            vec![Text::new(text_style, text)],
+        )
+    }
+    pub fn new_generic(
+        mut style: Style,
+        checked: bool,
+        kind: ToggleKind,
+        input: Box<dyn InputTrait>,
+        render_checkbox: RenderCheckbox<I>,
+        on_toggle: OnToggle<I>,
+        children: Widgets<I>,
+    ) -> Self {
+        let base = &mut style.base;
+        widen_pads(kind, &mut base.pad, base.font_size, &mut base.breakpoints);
+        let custom = CheckboxBase {
+            checked,
+            kind,
+            interaction: Interaction::None,
+            input,
+            render_checkbox,
+            on_toggle,
+        };
+        Self {
+            pos: Default::default(),
+            size: None,
+            content_size: None,
+            layout_state: LayoutState::default(),
+            measure: None,
+            describe: Some(describe),
+            style,
+            custom,
+            children,
+        }
+    }
+    pub fn checked(&self) -> bool {
+        self.custom.checked
+    }
+}
+
+impl<I: Clone + 'static> Interactable<I> for Checkbox<I> {
+    fn interact(&mut self) -> Vec<I> {
+        self.custom.interaction = interact(self.rect(), &self.custom.input);
+        if self.custom.interaction.is_clicked() {
+            self.custom.checked = !self.custom.checked;
+            vec![(self.custom.on_toggle)(self.custom.checked)]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+impl<I> Localizable for Checkbox<I> {
+    fn relocalize(&mut self) -> bool {
+        relocalize_children(&mut self.children)
+    }
+}
+
+impl<I> Renderable for Checkbox<I> {
+    fn render_interactive(&self, interaction: Interaction) {
+        (self.custom.render_checkbox)(self, interaction)
+    }
+}
+
+fn render_checkbox<I>(widget: &Checkbox<I>, _unused: Interaction) {
+    let style = widget.style();
+    let kind = widget.custom.kind;
+    let font_size = style.font_size;
+    let start = style.border.left + indicator_start(kind, style.pad.left, font_size);
+    let rect = indicator_rect(kind, widget.rect(), start, font_size);
+    let state_style = style.coloring.choose(widget.custom.interaction);
+    draw_indicator(kind, rect, widget.custom.checked, state_style);
+    for child in widget.children() {
+        if child.style().visible {
+            child.render_interactive(widget.custom.interaction);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elm::widget::compute_layout;
+    use crate::lazy::layout::LayoutStyle;
+    use crate::lazy::Border;
+    use crate::testing::{mock_measure, MockInput};
+    use crate::widgets::anchor::{Horizontal, Layout, Vertical};
+    use crate::widgets::checkbox::indicator_pad;
+    use macroquad::math::{vec2, Rect};
+
+    #[test]
+    fn test_toggle_message() {
+        let input = MockInput::default();
+        let label_style = Style {
+            base: crate::lazy::Style {
+                pad: Pad::new_symmetric(0.0),
+                ..Default::default()
+            },
+            border: Border::new_symmetric(0.0),
+        };
+        let label = Text::new_generic(label_style, "label".to_string(), mock_measure);
+        let mut switch = Checkbox::new_generic(
+            Style::default(),
+            false,
+            ToggleKind::Switch,
+            Box::new(Clone::clone(&input)),
+            render_checkbox,
+            |checked| checked,
+            vec![Box::new(label)],
+        );
+        let top_left = Layout::horizontal(Horizontal::Right, Vertical::Top);
+        compute_layout(&mut switch, Rect::new(0.0, 0.0, 500.0, 500.0), top_left);
+        let style = Style::default();
+        let label_size = switch.children()[0].size();
+        assert_eq!(label_size, vec2(60.0, 16.0));
+        let left = indicator_pad(ToggleKind::Switch, style.pad.left, style.font_size);
+        assert_eq!(left, 2.0 * style.pad.left + 32.0);
+        assert_eq!(
+            switch.size(),
+            style.inset().total() + vec2(left - style.pad.left, 0.0) + label_size
+        );
+        let label_left = switch.children()[0].rect().x;
+        assert_eq!(label_left, style.border.left + left);
+
+        assert_eq!(switch.interact(), Vec::<bool>::new());
+        input.click(switch.rect().center());
+        assert_eq!(switch.interact(), vec![true]);
+        assert_eq!(switch.interact(), vec![false]);
+        input.clear();
+        assert_eq!(switch.interact(), Vec::<bool>::new());
+        assert!(!switch.checked());
+    }
+}
@@ -1,8 +1,12 @@
-use crate::draw::{draw_rect, draw_rect_lines};
+use crate::draw::draw_rect;
 use crate::elm::style::Style;
 use crate::elm::widget::{
-    Interactable, Renderable, RenderableWidget, Widget, WidgetTrait, Widgets,
+    relocalize_children, Interactable, Localizable, Renderable, RenderableWidget, Widget,
+    WidgetTrait, Widgets,
 };
+use crate::lazy::dump::describe;
+use crate::lazy::grid::Grid;
+use crate::lazy::{draw_border, Arrangement};
 use crate::widgets::Interaction;
 
 pub type Container<I> = Widget<(), I>;
@@ -14,18 +18,35 @@ pub fn container<I: Clone + 'static, Sty: Into<Style>>(
     Container::new_raw(style, children)
 }
 
+/// A container that places its children in the cells of `grid`. See [`Style::cell`].
+pub fn grid<I: Clone + 'static, Sty: Into<Style>>(
+    style: Sty,
+    grid: Grid,
+    children: Widgets<I>,
+) -> Container<I> {
+    let mut style = style.into();
+    style.arrangement = Arrangement::Grid(grid);
+    Container::new_raw(style, children)
+}
+
+/// A container whose children are on top of each other. See [`Style::placement`].
+pub fn stack<I: Clone + 'static, Sty: Into<Style>>(
+    style: Sty,
+    children: Widgets<I>,
+) -> Container<I> {
+    let mut style = style.into();
+    style.arrangement = Arrangement::Stack;
+    Container::new_raw(style, children)
+}
+
 impl<I: Clone + 'static> Container<I> {
     pub fn new<Sty: Into<Style>>(style: Sty, children: Widgets<I>) -> Box<dyn RenderableWidget<I>> {
         Box::new(Self::new_raw(style, children))
     }
     pub fn new_raw<Sty: Into<Style>>(style: Sty, children: Widgets<I>) -> Container<I> {
-        Widget {
-            pos: Default::default(),
-            size: Default::default(),
-            style: style.into(),
-            custom: (),
-            children,
-        }
+        let mut container = Widget::container_custom(style.into(), (), children);
+        container.describe = Some(describe);
+        container
     }
 }
 
@@ -33,24 +54,83 @@ impl<I> Interactable<I> for Container<I> {
     fn interact(&mut self) -> Vec<I> {
         let mut messages = Vec::new();
         for child in &mut self.children {
-            messages.extend(child.interact());
+            if child.style().visible {
+                messages.extend(child.interact());
+            }
         }
         messages
     }
 }
 
+impl<I> Localizable for Container<I> {
+    fn relocalize(&mut self) -> bool {
+        relocalize_children(&mut self.children)
+    }
+}
+
 impl<I> Renderable for Container<I> {
     fn render_interactive(&self, parent_interaction: Interaction) {
         let widget = self;
         let state_style = widget.style().coloring.choose(parent_interaction);
         draw_rect(widget.rect(), state_style.bg_color);
-        draw_rect_lines(
-            widget.rect(),
-            widget.style.border * 2.0,
-            state_style.border_color,
-        );
+        draw_border(widget.rect(), widget.style.border, state_style.border_color);
         for child in &self.children {
-            child.render_interactive(parent_interaction);
+            if child.style().visible {
+                child.render_interactive(parent_interaction);
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elm::widget::compute_layout;
+    use crate::lazy::grid::Cell;
+    use crate::lazy::{Border, Pad, Size};
+    use crate::widgets::anchor::{Horizontal, Layout, Vertical};
+    use macroquad::math::{vec2, Rect};
+
+    /// Same as the lazy `test_grid_layout`.
+    #[test]
+    fn test_grid() {
+        let style = Style {
+            base: crate::lazy::Style {
+                pad: Pad::new_symmetric(10.0),
+                gap: 5.0,
+                ..Default::default()
+            },
+            border: Border::new_symmetric(0.0),
+        };
+        let leaf = |w, h, cell: Option<Cell>| -> Box<dyn RenderableWidget<()>> {
+            let mut leaf_style = style.clone();
+            leaf_style.size = Size::Fixed { w, h };
+            leaf_style.cell = cell;
+            Container::new(leaf_style, Vec::new())
+        };
+        let cells = Grid::auto(2).with_alignment(Horizontal::Left, Vertical::Top);
+        let corner = Cell::new(1, 1).align(Horizontal::Right, Vertical::Bottom);
+        let mut ui = grid(
+            style.clone(),
+            cells,
+            vec![
+                leaf(40.0, 20.0, None),
+                leaf(10.0, 10.0, Some(corner)),
+                leaf(20.0, 30.0, None),
+                leaf(30.0, 10.0, None),
+            ],
+        );
+        let top_left = Layout::vertical(Vertical::Bottom, Horizontal::Left);
+        compute_layout(&mut ui, Rect::new(0.0, 0.0, 1000.0, 500.0), top_left);
+        assert_eq!(ui.size(), vec2(85.0, 65.0));
+        let rects = ui
+            .children()
+            .iter()
+            .map(|child| child.rect())
+            .collect::<Vec<_>>();
+        assert_eq!(rects[0], Rect::new(10.0, 10.0, 40.0, 20.0));
+        assert_eq!(rects[1], Rect::new(65.0, 45.0, 10.0, 10.0));
+        assert_eq!(rects[2], Rect::new(55.0, 10.0, 20.0, 30.0));
+        assert_eq!(rects[3], Rect::new(10.0, 45.0, 30.0, 10.0));
+    }
+}
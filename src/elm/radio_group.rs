@@ -0,0 +1,359 @@
+use crate::draw::draw_rect;
+use crate::elm::style::Style;
+use crate::elm::text::draw_text;
+use crate::elm::widget::{
+    Interactable, Localizable, Renderable, RenderableWidget, Widget, WidgetTrait,
+};
+use crate::i18n::{self, Localized};
+use crate::input::input_macroquad::InputMacroquad;
+use crate::input::input_trait::InputTrait;
+use crate::lazy::dump::{describe, Describe};
+use crate::lazy::layout::LayoutStyle;
+use crate::lazy::{draw_border, Dirty, LayoutState};
+use crate::widgets::anchor::{Anchor, Horizontal, Layout};
+use crate::widgets::button_group::{Direction, LabelGroup};
+use crate::widgets::radio_group::select_with_keys;
+use crate::widgets::rect_cut::{split_columns, split_rows};
+use crate::widgets::text::{MeasureText, TextRect};
+use crate::widgets::{interact, Interaction};
+use crate::SizeInPixels2d;
+use macroquad::input::KeyCode;
+use macroquad::math::{vec2, Rect};
+
+/// A radio group or segmented control, see [`crate::widgets::radio_group`]. The options are side
+/// by side if the layout of the style is horizontal, and one below the other otherwise. They are
+/// sized by a [`LabelGroup`], so all of them have the size of the widest one, plus the border
+/// and the pad of the style.
+pub type RadioGroup<I> = Widget<RadioGroupBase<I>, I>;
+pub type RenderRadioGroup<I> = fn(widget: &RadioGroup<I>, interaction: Interaction);
+
+pub struct RadioGroupBase<I> {
+    /// the text of each option, laid out by a [`LabelGroup`] at the top left corner of the widget
+    pub labels: Vec<TextRect>,
+    /// emitted when the option with the same index is selected
+    pub messages: Vec<I>,
+    selected: usize,
+    /// if true, the arrow keys change the selection. Off by default, as every radio group on
+    /// screen would react to the same keys
+    pub keyboard: bool,
+    pub interactions: Vec<Interaction>,
+    pub input: Box<dyn InputTrait>,
+    pub render_radio_group: RenderRadioGroup<I>,
+    measure_text: MeasureText,
+}
+
+impl<I> Describe for RadioGroupBase<I> {
+    fn kind(&self) -> &'static str {
+        "RadioGroup"
+    }
+    fn text(&self) -> Option<&str> {
+        Some(&self.labels[self.selected].text)
+    }
+}
+
+impl<I: Clone + 'static> RadioGroup<I> {
+    /// Emits `on_select` of the index of the newly selected option.
+    pub fn new<Sty: Into<Style>, S: AsRef<str>>(
+        style: Sty,
+        texts: &[S],
+        selected: usize,
+        on_select: fn(usize) -> I,
+    ) -> Box<dyn RenderableWidget<I>> {
+        Box::new(Self::new_raw(style, texts, selected, on_select))
+    }
+    pub fn new_raw<Sty: Into<Style>, S: AsRef<str>>(
+        style: Sty,
+        texts: &[S],
+        selected: usize,
+        on_select: fn(usize) -> I,
+    ) -> Self {
+        let options = texts
+            .iter()
+            .enumerate()
+            .map(|(i, text)| (text.as_ref(), on_select(i)))
+            .collect();
+        Self::new_options_raw(style, options, selected)
+    }
+    /// Each option is a text and the message emitted when it's selected, e.g. a variant of a
+    /// user enum.
+    pub fn new_options<Sty: Into<Style>, S: AsRef<str>>(
+        style: Sty,
+        options: Vec<(S, I)>,
+        selected: usize,
+    ) -> Box<dyn RenderableWidget<I>> {
+        Box::new(Self::new_options_raw(style, options, selected))
+    }
+    pub fn new_options_raw<Sty: Into<Style>, S: AsRef<str>>(
+        style: Sty,
+        options: Vec<(S, I)>,
+        selected: usize,
+    ) -> Self {
+        Self::new_generic(
+            style.into(),
+            options,
+            selected,
+            Box::new(InputMacroquad),
+            macroquad::prelude::measure_text,
+            render_radio_group,
+        )
+    }
+    /// Like [`Self::new_options`], with texts that follow the language, see [`crate::i18n`].
+    pub fn new_localized_options<Sty: Into<Style>>(
+        style: Sty,
+        options: Vec<(Localized, I)>,
+        selected: usize,
+    ) -> Box<dyn RenderableWidget<I>> {
+        Box::new(Self::new_localized_options_raw(style, options, selected))
+    }
+    pub fn new_localized_options_raw<Sty: Into<Style>>(
+        style: Sty,
+        options: Vec<(Localized, I)>,
+        selected: usize,
+    ) -> Self {
+        let (keys, messages): (Vec<_>, Vec<_>) = options.into_iter().unzip();
+        let texts = keys.iter().map(Localized::resolve).zip(messages).collect();
+        let mut radio_group = Self::new_options_raw(style, texts, selected);
+        let language_revision = i18n::revision();
+        for (label, key) in radio_group.custom.labels.iter_mut().zip(keys) {
+            label.localized = Some(key);
+            label.language_revision = language_revision;
+        }
+        radio_group
+    }
+    /// `selected` is clamped to the available options.
+    pub fn new_generic<S: AsRef<str>>(
+        style: Style,
+        options: Vec<(S, I)>,
+        selected: usize,
+        input: Box<dyn InputTrait>,
+        measure_text: MeasureText,
+        render_radio_group: RenderRadioGroup<I>,
+    ) -> Self {
+        assert!(
+            !options.is_empty(),
+            "a radio group needs at least one option"
+        );
+        let (texts, messages): (Vec<_>, Vec<_>) = options
+            .into_iter()
+            .map(|(text, message)| (text.as_ref().to_string(), message))
+            .unzip();
+        let custom = RadioGroupBase {
+            selected: selected.min(texts.len() - 1),
+            interactions: vec![Interaction::None; texts.len()],
+            labels: Vec::new(),
+            messages,
+            keyboard: false,
+            input,
+            render_radio_group,
+            measure_text,
+        };
+        let mut radio_group = Self {
+            pos: Default::default(),
+            size: None,
+            content_size: None,
+            layout_state: LayoutState::default(),
+            measure: Some(Self::measure),
+            describe: Some(describe),
+            style,
+            custom,
+            children: Vec::new(),
+        };
+        radio_group.custom.labels = radio_group.label_group().create_vec(texts);
+        radio_group.measure();
+        radio_group
+    }
+}
+
+impl<I> RadioGroup<I> {
+    pub fn selected(&self) -> usize {
+        self.custom.selected
+    }
+    /// Returns whether the selection changed. Out of range options are ignored.
+    pub fn select(&mut self, option: usize) -> bool {
+        let changed = option != self.custom.selected && option < self.custom.labels.len();
+        if changed {
+            self.custom.selected = option;
+        }
+        changed
+    }
+    /// The rect of each option.
+    pub fn segments(&self) -> Vec<Rect> {
+        let count = self.custom.labels.len();
+        if self.horizontal() {
+            split_columns(self.rect(), count, 0.0)
+        } else {
+            split_rows(self.rect(), count, 0.0)
+        }
+    }
+    fn horizontal(&self) -> bool {
+        matches!(self.style.layout, Layout::Horizontal { .. })
+    }
+    /// Lays out the options like the style says. [`LabelGroup`] pads both sides the same, so
+    /// uneven sides are averaged.
+    fn label_group(&self) -> LabelGroup {
+        let style = &self.style;
+        let pad = style.inset().total() * 0.5;
+        LabelGroup {
+            font_size: style.font_size,
+            font: style.font,
+            alignment: Horizontal::Center,
+            direction: if self.horizontal() {
+                Direction::Right
+            } else {
+                Direction::Bottom
+            },
+            anchor: Anchor::top_left(0.0, 0.0),
+            pad_x: Some(pad.x),
+            pad_y: Some(pad.y),
+            margin: 0.0,
+            measure_text: self.custom.measure_text,
+        }
+    }
+    fn measure(&mut self) {
+        let label_group = self.label_group();
+        label_group.relayout(&mut self.custom.labels);
+        self.fit_labels();
+    }
+    fn fit_labels(&mut self) {
+        let size = self
+            .custom
+            .labels
+            .iter()
+            .map(|label| label.rect.point() + label.rect.size())
+            .fold(SizeInPixels2d::ZERO, SizeInPixels2d::max);
+        self.content_size = Some(size);
+        self.size = self.content_size;
+        self.layout_state.dirty = Dirty::Size;
+    }
+}
+
+impl<I: Clone + 'static> Interactable<I> for RadioGroup<I> {
+    fn interact(&mut self) -> Vec<I> {
+        let mut selected = self.custom.selected;
+        for (i, segment) in self.segments().into_iter().enumerate() {
+            let interaction = interact(segment, &self.custom.input);
+            if interaction.is_clicked() {
+                selected = i;
+            }
+            self.custom.interactions[i] = interaction;
+        }
+        if self.custom.keyboard {
+            let (next, previous) = if self.horizontal() {
+                (KeyCode::Right, KeyCode::Left)
+            } else {
+                (KeyCode::Down, KeyCode::Up)
+            };
+            let count = self.custom.labels.len();
+            selected =
+                select_with_keys(self.custom.input.as_ref(), selected, count, next, previous);
+        }
+        if self.select(selected) {
+            vec![self.custom.messages[selected].clone()]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+impl<I> Localizable for RadioGroup<I> {
+    fn relocalize(&mut self) -> bool {
+        let label_group = self.label_group();
+        if !label_group.relocalize(&mut self.custom.labels) {
+            return false;
+        }
+        self.fit_labels();
+        true
+    }
+}
+
+impl<I> Renderable for RadioGroup<I> {
+    fn render_interactive(&self, interaction: Interaction) {
+        (self.custom.render_radio_group)(self, interaction)
+    }
+}
+
+/// Draws the selected option with the pressed style.
+fn render_radio_group<I>(widget: &RadioGroup<I>, _unused: Interaction) {
+    let style = widget.style();
+    let custom = &widget.custom;
+    for (i, segment) in widget.segments().into_iter().enumerate() {
+        let state_style = if i == custom.selected {
+            &style.coloring.pressed
+        } else {
+            style.coloring.choose(custom.interactions[i])
+        };
+        draw_rect(segment, state_style.bg_color);
+        draw_border(segment, style.border, state_style.border_color);
+        let label = &custom.labels[i];
+        let pad = vec2(
+            (segment.w - label.text_width) * 0.5,
+            (segment.h - label.reference_height) * 0.5,
+        );
+        draw_text(
+            &label.text,
+            segment.point(),
+            pad,
+            label.reference_height,
+            style.font_size,
+            style.font,
+            *state_style,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elm::widget::compute_layout;
+    use crate::lazy::Pad;
+    use crate::testing::{mock_measure, MockInput};
+    use crate::widgets::anchor::{Horizontal, Vertical};
+
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    enum Difficulty {
+        Easy,
+        Normal,
+        Hard,
+    }
+
+    #[test]
+    fn test_selection() {
+        let input = MockInput::default();
+        let layout = Layout::horizontal(Horizontal::Right, Vertical::Top);
+        let style = Style::from(crate::lazy::Style {
+            layout,
+            font_size: 20.0,
+            pad: Pad::new(5.0, 0.0),
+            ..Default::default()
+        });
+        let options = vec![
+            ("easy", Difficulty::Easy),
+            ("normal", Difficulty::Normal),
+            ("hard", Difficulty::Hard),
+        ];
+        let mut group = RadioGroup::new_generic(
+            style,
+            options,
+            1,
+            Box::new(Clone::clone(&input)),
+            mock_measure,
+            render_radio_group,
+        );
+        compute_layout(&mut group, Rect::new(0.0, 0.0, 500.0, 500.0), layout);
+        // "normal" is 90 pixels wide, plus a pad of 5 and a border of 1 on each side
+        assert_eq!(group.rect(), Rect::new(0.0, 0.0, 306.0, 22.0));
+        assert_eq!(group.segments()[2], Rect::new(204.0, 0.0, 102.0, 22.0));
+
+        assert_eq!(group.interact(), Vec::new());
+        input.click(vec2(250.0, 10.0));
+        assert_eq!(group.interact(), vec![Difficulty::Hard]);
+        assert_eq!(group.interact(), Vec::new());
+        input.press_key(KeyCode::Right);
+        assert_eq!(group.interact(), Vec::new());
+        group.custom.keyboard = true;
+        assert_eq!(group.interact(), vec![Difficulty::Easy]);
+        input.press_key(KeyCode::Left);
+        assert_eq!(group.interact(), vec![Difficulty::Hard]);
+        assert_eq!(group.selected(), 2);
+    }
+}
@@ -0,0 +1,151 @@
+use crate::clip;
+use crate::draw::draw_rect;
+use crate::elm::container::Container;
+use crate::elm::style::Style;
+use crate::elm::widget::{
+    relocalize_children, set_positions, Interactable, Localizable, Renderable, RenderableWidget,
+    WidgetTrait, Widgets, WidgetsView, WidgetsViewMut,
+};
+use crate::input::input_macroquad::InputMacroquad;
+use crate::input::input_trait::InputTrait;
+use crate::lazy::layout::LayoutStyle;
+use crate::lazy::scroll::{content_extent, ScrollState};
+use crate::lazy::{add_contour, draw_border, Dirty, LayoutState};
+use crate::widgets::anchor::Anchor;
+use crate::widgets::Interaction;
+use crate::{PositionInPixels2d, SizeInPixels2d};
+
+/// A container that shows only the part of its children that fits in it. See
+/// [`crate::lazy::scroll`] for how it scrolls. Give it a [`crate::lazy::Size`] other than `Fit`
+/// (or a `max_size`) so that it's smaller than its children.
+pub struct Scroll<I> {
+    pub container: Container<I>,
+    pub state: ScrollState,
+    pub input: Box<dyn InputTrait>,
+}
+
+pub fn scroll<I: Clone + 'static, Sty: Into<Style>>(style: Sty, children: Widgets<I>) -> Scroll<I> {
+    Scroll::new_raw(style, children)
+}
+
+impl<I: Clone + 'static> Scroll<I> {
+    pub fn new<Sty: Into<Style>>(style: Sty, children: Widgets<I>) -> Box<dyn RenderableWidget<I>> {
+        Box::new(Self::new_raw(style, children))
+    }
+    pub fn new_raw<Sty: Into<Style>>(style: Sty, children: Widgets<I>) -> Self {
+        Self::new_generic(style.into(), children, Box::new(InputMacroquad))
+    }
+    pub fn new_generic(style: Style, children: Widgets<I>, input: Box<dyn InputTrait>) -> Self {
+        Self {
+            container: Container::new_raw(style, children),
+            state: ScrollState::default(),
+            input,
+        }
+    }
+}
+
+impl<I> Scroll<I> {
+    pub fn content_extent(&self) -> SizeInPixels2d {
+        let children = self.children();
+        let margined = children
+            .iter()
+            .filter(|child| child.style().visible)
+            .map(|child| add_contour(child.rect(), child.style().margin));
+        content_extent(
+            self.rect(),
+            self.state.offset,
+            self.style().inset(),
+            margined,
+        )
+    }
+}
+
+impl<I> WidgetTrait<I> for Scroll<I> {
+    fn size(&self) -> SizeInPixels2d {
+        self.container.size()
+    }
+    fn pos(&self) -> PositionInPixels2d {
+        self.container.pos()
+    }
+    fn set_pos(&mut self, position: PositionInPixels2d) {
+        self.container.set_pos(position)
+    }
+    fn set_size(&mut self, size: SizeInPixels2d) {
+        self.container.set_size(size)
+    }
+    fn content_offset(&self) -> SizeInPixels2d {
+        self.state.offset
+    }
+    fn layout_state(&self) -> LayoutState {
+        self.container.layout_state()
+    }
+    fn set_layout_state(&mut self, state: LayoutState) {
+        self.container.set_layout_state(state)
+    }
+    fn respond(&mut self, available: SizeInPixels2d) -> bool {
+        self.container.respond(available)
+    }
+    fn kind(&self) -> &'static str {
+        "Scroll"
+    }
+    fn clips_children(&self) -> bool {
+        true
+    }
+    fn style(&self) -> &Style {
+        &self.container.style
+    }
+
+    fn children(&self) -> WidgetsView<'_, I> {
+        self.container.children()
+    }
+    fn children_mut(&mut self) -> WidgetsViewMut<'_, I> {
+        self.container.children_mut()
+    }
+}
+
+impl<I> Interactable<I> for Scroll<I> {
+    fn interact(&mut self) -> Vec<I> {
+        let viewport = self.rect();
+        let extent = self.content_extent();
+        if self.state.update(viewport, extent, self.input.as_ref()) {
+            let margin = self.style().margin.start();
+            self.mark_dirty(Dirty::Position);
+            set_positions(self, Anchor::top_left_v(viewport.point() - margin));
+        }
+        let input_clip = self.state.input_clip(viewport, extent, self.input.as_ref());
+        clip::with_input_clip(input_clip, || {
+            let mut messages = Vec::new();
+            for child in &mut self.container.children {
+                if child.style().visible {
+                    messages.extend(child.interact());
+                }
+            }
+            messages
+        })
+    }
+}
+
+impl<I> Localizable for Scroll<I> {
+    fn relocalize(&mut self) -> bool {
+        relocalize_children(&mut self.container.children)
+    }
+}
+
+impl<I> Renderable for Scroll<I> {
+    fn render_interactive(&self, parent_interaction: Interaction) {
+        let viewport = self.rect();
+        let style = self.style();
+        let state_style = style.coloring.choose(parent_interaction);
+        draw_rect(viewport, state_style.bg_color);
+        clip::with_render_clip(viewport, || {
+            for child in &self.container.children {
+                if child.style().visible {
+                    child.render_interactive(parent_interaction);
+                }
+            }
+        });
+        self.state
+            .render_scrollbars(viewport, self.content_extent(), &style.coloring);
+        draw_border(viewport, style.border, state_style.border_color);
+    }
+}
@@ -1,10 +1,13 @@
+use crate::clip;
 use crate::draw::{draw_rect, draw_rect_lines};
 use crate::elm::style::Style;
 use crate::elm::widget::{
-    Interactable, Renderable, RenderableWidget, Widget, WidgetTrait,
+    Interactable, Localizable, Renderable, RenderableWidget, Widget, WidgetTrait,
 };
 use crate::input::input_macroquad::InputMacroquad;
 use crate::input::input_trait::InputTrait;
+use crate::lazy::dump::{describe, Describe};
+use crate::lazy::LayoutState;
 use crate::widgets::Interaction;
 use crate::SizeInPixels2d;
 use macroquad::input::MouseButton;
@@ -27,6 +30,12 @@ pub struct SliderBase<I> {
     pub on_press: OnPress<I>,
 }
 
+impl<I> Describe for SliderBase<I> {
+    fn kind(&self) -> &'static str {
+        "Slider"
+    }
+}
+
 impl<I: Clone + 'static> Slider<I> {
     pub fn new<Sty: Into<Style>>(
         style: Sty,
@@ -59,7 +68,7 @@ impl<I: Clone + 'static> Slider<I> {
         )
     }
     pub fn new_generic(
-        style: Style,
+        mut style: Style,
         min: f32,
         max: f32,
         current: f32,
@@ -68,6 +77,7 @@ impl<I: Clone + 'static> Slider<I> {
         render_slider: RenderSlider<I>,
         on_press: OnPress<I>,
     ) -> Self {
+        style.min_size = style.min_size.max(min_size);
         let custom = SliderBase {
             min,
             max,
@@ -81,6 +91,10 @@ impl<I: Clone + 'static> Slider<I> {
         Self {
             pos: Default::default(),
             size: Some(min_size),
+            content_size: Some(min_size),
+            layout_state: LayoutState::default(),
+            measure: None,
+            describe: Some(describe),
             style,
             custom,
             children: vec![],
@@ -105,7 +119,7 @@ impl<I: Clone + 'static> Interactable<I> for Slider<I> {
         let handle_width = self.handle_width();
         let mouse_coef =
             (mouse_pos - rect.point() - 0.5 * handle_width) / (rect.size() - handle_width);
-        let (interaction, render_pos) = if rect.contains(mouse_pos) {
+        let (interaction, render_pos) = if clip::clip_rect(rect).contains(mouse_pos) {
             if input.is_mouse_button_down(MouseButton::Left) {
                 (Interaction::Pressing, mouse_coef.x)
             } else if input.is_mouse_button_released(MouseButton::Left) {
@@ -128,6 +142,8 @@ impl<I: Clone + 'static> Interactable<I> for Slider<I> {
     }
 }
 
+impl<I> Localizable for Slider<I> {}
+
 impl<I> Renderable for Slider<I> {
     fn render_interactive(&self, interaction: Interaction) {
         (self.custom.render_slider)(self, interaction);
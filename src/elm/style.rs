@@ -1,18 +1,14 @@
-use crate::lazy::{Margin, Pad, Size, DEFAULT_FONT_SIZE};
-use crate::widgets::anchor::{Horizontal, Layout, Vertical};
-use crate::widgets::Coloring;
-use macroquad::prelude::Font;
+use crate::lazy::layout::LayoutStyle;
+use crate::lazy::Border;
+use std::ops::{Deref, DerefMut};
 
+/// A [`crate::lazy::Style`] with a border. It derefs to the lazy style, so its fields and methods
+/// are available directly, e.g. `style.pad` or `style.respond(available)`.
 #[derive(Clone)]
 pub struct Style {
-    pub pad: Pad,
-    pub margin: Margin,
-    pub border: f32,
-    pub layout: Layout,
-    pub font_size: f32,
-    pub font: Option<Font>,
-    pub size: Size,
-    pub coloring: Coloring,
+    pub base: crate::lazy::Style,
+    /// drawn at the edge of the widget, outside the pad, so the content starts after both
+    pub border: Border,
 }
 
 impl Into<Style> for &Style {
@@ -23,15 +19,38 @@ impl Into<Style> for &Style {
 
 impl Default for Style {
     fn default() -> Self {
+        Self::from(crate::lazy::Style::default())
+    }
+}
+
+/// With the default border.
+impl From<crate::lazy::Style> for Style {
+    fn from(base: crate::lazy::Style) -> Self {
         Self {
-            pad: Pad::new(DEFAULT_FONT_SIZE * 1.5, DEFAULT_FONT_SIZE),
-            margin: Margin::new_symmetric(0.0),
-            border: 1.0,
-            layout: Layout::vertical(Vertical::Bottom, Horizontal::Center),
-            font_size: DEFAULT_FONT_SIZE,
-            font: None,
-            size: Size::Fit,
-            coloring: Coloring::default(),
+            base,
+            border: Border::new_symmetric(1.0),
         }
     }
 }
+
+impl Deref for Style {
+    type Target = crate::lazy::Style;
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Style {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl LayoutStyle for Style {
+    fn base(&self) -> &crate::lazy::Style {
+        &self.base
+    }
+    fn border(&self) -> Border {
+        self.border
+    }
+}
@@ -1,15 +1,56 @@
 use crate::elm::style::Style;
-use crate::elm::widget::{Interactable, Renderable, RenderableWidget, Widget};
-use crate::lazy::text::size_text_generic;
+use crate::elm::widget::{Interactable, Localizable, Renderable, RenderableWidget, Widget};
+use crate::fonts::FontId;
+use crate::i18n;
+use crate::i18n::Localized;
+use crate::lazy::dump::{describe, Describe};
+use crate::lazy::layout::LayoutStyle;
+use crate::lazy::text::{fit_fixed_size, limit_text_width, size_text_generic};
+use crate::lazy::{Dirty, LayoutState, Size};
+use crate::widgets;
+use crate::widgets::text::{FittedText, MeasureText};
 use crate::widgets::{Interaction, StateColor};
-use macroquad::math::Vec2;
-use macroquad::prelude::{Font, TextParams};
+use macroquad::math::{vec2, Vec2};
 
 pub type Text<I> = Widget<TextBase, I>;
 
 pub struct TextBase {
     text: String,
+    /// `text` and the font size chosen to fit a fixed size, see [`Style::auto_fit`]
+    fitted: Option<FittedText>,
+    /// the text cut with "..." to fit the width it was given, see [`Style::ellipsis`]
+    shown: Option<String>,
     reference_height: f32,
+    measure_text: MeasureText,
+    /// if Some, `text` is its translation at the time of `language_revision`
+    localized: Option<Localized>,
+    language_revision: u64,
+}
+
+impl Describe for TextBase {
+    fn kind(&self) -> &'static str {
+        "Text"
+    }
+    fn text(&self) -> Option<&str> {
+        Some(self.shown())
+    }
+}
+
+impl TextBase {
+    /// The text as rendered, which might be cut.
+    pub fn shown(&self) -> &str {
+        match (&self.shown, &self.fitted) {
+            (Some(shown), _) => shown,
+            (None, Some(fitted)) => &fitted.text,
+            (None, None) => &self.text,
+        }
+    }
+    /// The font size it's rendered with, which is `style_font_size` unless it was fitted.
+    pub fn font_size(&self, style_font_size: f32) -> f32 {
+        self.fitted
+            .as_ref()
+            .map_or(style_font_size, |fitted| fitted.font_size)
+    }
 }
 
 impl<I: 'static> Text<I> {
@@ -23,53 +64,144 @@ impl<I: 'static> Text<I> {
         style: Sty,
         text: Str,
     ) -> Widget<TextBase, I> {
-        let style = style.into();
-        let text = text.into();
-        let size = size_text_generic(
-            &text,
-            macroquad::prelude::measure_text,
-            style.font.as_ref(),
-            style.font_size,
-        );
-
+        Self::new_generic(style.into(), text.into(), macroquad::prelude::measure_text)
+    }
+    pub fn new_localized<Sty: Into<Style>>(
+        style: Sty,
+        localized: Localized,
+    ) -> Box<dyn RenderableWidget<I>> {
+        Box::new(Self::new_localized_raw(style, localized))
+    }
+    pub fn new_localized_raw<Sty: Into<Style>>(
+        style: Sty,
+        localized: Localized,
+    ) -> Widget<TextBase, I> {
+        Self::new_localized_generic(style.into(), localized, macroquad::prelude::measure_text)
+    }
+    pub fn new_localized_generic(
+        style: Style,
+        localized: Localized,
+        measure_text: MeasureText,
+    ) -> Self {
+        let language_revision = i18n::revision();
+        let mut text = Self::new_generic(style, localized.resolve(), measure_text);
+        text.custom.localized = Some(localized);
+        text.custom.language_revision = language_revision;
+        text
+    }
+    pub fn new_generic(style: Style, text: String, measure_text: MeasureText) -> Self {
         let custom = TextBase {
-            text: text.into(),
-            reference_height: size.y,
+            text,
+            fitted: None,
+            shown: None,
+            reference_height: 0.0,
+            measure_text,
+            localized: None,
+            language_revision: 0,
         };
-
-        let size = Some(size + 2.0 * style.pad.vec2());
-        Text {
+        let mut text = Text {
             style,
-            size,
+            size: None,
+            content_size: None,
+            layout_state: LayoutState::default(),
+            measure: Some(Self::measure),
+            describe: Some(describe),
             pos: Default::default(),
             custom,
             children: Vec::new(),
+        };
+        text.measure();
+        text
+    }
+}
+impl<I> Text<I> {
+    pub fn text(&self) -> &str {
+        &self.custom.text
+    }
+    fn measure(&mut self) {
+        let style = &self.style;
+        let measure_text = self.custom.measure_text;
+        self.custom.fitted = fit_fixed_size(
+            &self.custom.text,
+            style.size,
+            style.inset(),
+            style.auto_fit,
+            style.font,
+            measure_text,
+        );
+        let font_size = self.custom.font_size(style.font_size);
+        let text = match &self.custom.fitted {
+            Some(fitted) => &fitted.text,
+            None => &self.custom.text,
+        };
+        let mut size = size_text_generic(text, measure_text, style.font, font_size);
+        self.custom.shown = None;
+        if let Some((shown, shown_size)) = limit_text_width(
+            text,
+            size,
+            self.layout_state.width_limit,
+            style.inset(),
+            style.font,
+            font_size,
+            measure_text,
+        ) {
+            self.custom.shown = Some(shown);
+            size = shown_size;
+        }
+        self.custom.reference_height = size.y;
+        self.content_size = Some(if let Size::Fixed { w, h } = style.size {
+            vec2(w, h)
+        } else {
+            size + style.inset().total()
+        });
+        self.size = self.content_size;
+        self.layout_state.dirty = Dirty::Size;
+    }
+}
+impl<I> Localizable for Text<I> {
+    fn relocalize(&mut self) -> bool {
+        let language_revision = i18n::revision();
+        if self.custom.language_revision == language_revision {
+            return false;
+        }
+        if let Some(localized) = &self.custom.localized {
+            let text = localized.resolve();
+            self.custom.language_revision = language_revision;
+            if text == self.custom.text {
+                return false;
+            }
+            self.custom.text = text;
+            self.measure();
+            true
+        } else {
+            false
         }
     }
 }
 impl<I> Renderable for Text<I> {
     fn render_interactive(&self, parent_interaction: Interaction) {
         draw_text(
-            &self.custom.text,
+            self.custom.shown(),
             self.pos,
-            self.style.pad.vec2(),
+            self.style.inset().start(),
             self.custom.reference_height,
-            self.style.font_size,
-            self.style.font.as_ref(),
+            self.custom.font_size(self.style.font_size),
+            self.style.font,
             *self.style.coloring.choose(parent_interaction),
         );
     }
 }
 impl<I> Interactable<I> for Text<I> {}
 
-/// Here the position is of the border. The top left corner of the text is at pos + pad.
+/// Here the position is of the border. The top left corner of the text is at pos + pad, where
+/// the pad includes the border.
 pub fn draw_text(
     text: &str,
     pos: Vec2,
     pad: Vec2,
     reference_height: f32,
     font_size: f32,
-    font: Option<&Font>,
+    font: FontId,
     state_color: StateColor,
 ) {
     // macroquad's draw_text() draws from the baseline of the text
@@ -82,11 +214,36 @@ pub fn draw_text(
     let x = text_pos.x.round();
     let y = (text_pos.y + approx_height_from_baseline_to_top).round();
 
-    let params = TextParams {
-        font,
-        font_size: font_size as u16,
-        color: state_color.text_color,
-        ..TextParams::default()
-    };
-    macroquad::text::draw_text_ex(text, x, y, params);
+    widgets::text::draw_text(text, x, y, font_size, &state_color, font);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lazy::{Border, Pad};
+    use crate::testing::mock_measure;
+
+    #[test]
+    fn test_relocalize() {
+        i18n::load_language_str("en", "exit = Exit").unwrap();
+        i18n::load_language_str("es", "exit = Salir").unwrap();
+        i18n::set_language("en");
+        let style = Style {
+            base: crate::lazy::Style {
+                pad: Pad::new_symmetric(0.0),
+                ..Default::default()
+            },
+            border: Border::new_symmetric(0.0),
+        };
+        let mut text = Text::<()>::new_localized_generic(style, "exit".into(), mock_measure);
+        assert_eq!(text.text(), "Exit");
+        assert_eq!(text.content_size, Some(vec2(48.0, 16.0)));
+        assert!(!text.relocalize());
+
+        i18n::set_language("es");
+        assert!(text.relocalize());
+        assert_eq!(text.text(), "Salir");
+        assert_eq!(text.content_size, Some(vec2(60.0, 16.0)));
+        assert!(!text.relocalize());
+    }
 }
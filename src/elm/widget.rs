@@ -1,9 +1,12 @@
-use crate::draw::to_rect;
 use crate::elm::style::Style;
+use crate::lazy::dump::{describe_kind, describe_text, dump_node, DescribeFn};
+use crate::lazy::overflow::Overflow;
+use crate::lazy::{layout, Dirty, LayoutState};
 use crate::widgets::anchor::{Anchor, Layout};
+use crate::widgets::text::Pixels;
 use crate::widgets::Interaction;
 use crate::{PositionInPixels2d, SizeInPixels2d};
-use macroquad::math::{vec2, Rect};
+use macroquad::math::Rect;
 
 pub type Widgets<I> = Vec<Box<dyn RenderableWidget<I>>>;
 // pub type WidgetsViewMut<'a, 'b> = Vec<&'a mut (dyn RenderableWidget + 'b)>;
@@ -24,10 +27,56 @@ pub trait WidgetTrait<I> {
     }
     fn set_pos(&mut self, position: PositionInPixels2d);
     fn set_size(&mut self, size: SizeInPixels2d);
+    /// The size that leaves need for their own content (e.g. a text), pad included. Containers
+    /// return None, and their size is computed from their children in [`set_sizes`].
+    fn content_size(&self) -> Option<SizeInPixels2d> {
+        None
+    }
+    /// How much the children are moved left and up from where the layout would put them, e.g.
+    /// by scrolling.
+    fn content_offset(&self) -> SizeInPixels2d {
+        SizeInPixels2d::ZERO
+    }
+    /// Widgets that don't keep a [`LayoutState`] are laid out again every time.
+    fn layout_state(&self) -> LayoutState {
+        LayoutState::default()
+    }
+    fn set_layout_state(&mut self, _state: LayoutState) {}
+    /// Switches to the variant of the style for the `available` space (see [`Style::respond`]).
+    /// Returns whether the style changed.
+    fn respond(&mut self, _available: SizeInPixels2d) -> bool {
+        false
+    }
+    /// Limits the width (pad included) of the content of leaves that allow it, like texts with
+    /// [`Style::ellipsis`], and measures them again if their style changed. Returns whether the
+    /// content size changed.
+    fn limit_width(&mut self, _width: Pixels) -> bool {
+        false
+    }
+    /// Whether the children can go beyond this widget on purpose, like in a scroll container, so
+    /// that they are not reported as overflows.
+    fn clips_children(&self) -> bool {
+        false
+    }
+    /// Makes the next layout compute again at least `dirty` for this widget. Call it after
+    /// changing the style or the children of a widget.
+    fn mark_dirty(&mut self, dirty: Dirty) {
+        let mut state = self.layout_state();
+        state.dirty = state.dirty.max(dirty);
+        self.set_layout_state(state);
+    }
     fn reanchor(&mut self, anchor: Anchor) {
         let new_rect = anchor.get_top_left_pixel(self.size());
         self.set_pos(new_rect);
     }
+    /// Name of the kind of widget, for [`dump`].
+    fn kind(&self) -> &'static str {
+        "Widget"
+    }
+    /// The text shown by this widget itself (not by its children), for [`dump`].
+    fn text(&self) -> Option<&str> {
+        None
+    }
     fn style(&self) -> &Style;
 
     fn children(&self) -> WidgetsView<'_, I>;
@@ -51,18 +100,61 @@ pub trait Interactable<I> {
     }
 }
 
-pub trait RenderableWidget<I>: Renderable + WidgetTrait<I> + Interactable<I> {}
+pub trait Localizable {
+    /// Translates and measures again the texts if the language changed. Returns whether any text
+    /// changed, in which case the layout has to be computed again: measuring resets the size that
+    /// the layout gave, even if the natural size is the same.
+    fn relocalize(&mut self) -> bool {
+        false
+    }
+}
+
+pub trait RenderableWidget<I>: Renderable + WidgetTrait<I> + Interactable<I> + Localizable {}
 
-impl<I, T: Renderable + WidgetTrait<I> + Interactable<I>> RenderableWidget<I> for T {}
+impl<I, T: Renderable + WidgetTrait<I> + Interactable<I> + Localizable> RenderableWidget<I> for T {}
 
 pub struct Widget<Custom, I> {
     pub custom: Custom,
     pub pos: PositionInPixels2d,
     pub size: Option<SizeInPixels2d>,
+    pub content_size: Option<SizeInPixels2d>,
+    pub layout_state: LayoutState,
+    /// computes `content_size` again after the style changed, for leaves whose content depends
+    /// on it, like texts
+    pub measure: Option<fn(&mut Self)>,
+    /// names the widget in dumps, see [`DescribeFn`]
+    pub describe: Option<DescribeFn<Custom>>,
+    /// prefer [`Self::style_mut`] to change it, so the widget is measured again
     pub style: Style,
     pub children: Widgets<I>,
 }
 
+impl<C, I> Widget<C, I> {
+    pub fn container_custom(style: Style, custom: C, children: Widgets<I>) -> Self {
+        Self {
+            custom,
+            pos: Default::default(),
+            size: Default::default(),
+            content_size: None,
+            layout_state: LayoutState::default(),
+            measure: None,
+            describe: None,
+            style,
+            children,
+        }
+    }
+    pub fn leaf_custom(style: Style, custom: C) -> Self {
+        Self::container_custom(style, custom, Vec::new())
+    }
+    /// Marks the widget to be measured again in the next layout. Changing
+    /// [`Self::style`] directly keeps the previous measurements until something else marks
+    /// the widget.
+    pub fn style_mut(&mut self) -> &mut Style {
+        self.layout_state.dirty = Dirty::Size;
+        &mut self.style
+    }
+}
+
 impl<C, I> WidgetTrait<I> for Widget<C, I> {
     fn size(&self) -> SizeInPixels2d {
         if let Some(size) = self.size {
@@ -78,9 +170,44 @@ impl<C, I> WidgetTrait<I> for Widget<C, I> {
         self.pos = position;
     }
     fn set_size(&mut self, size: SizeInPixels2d) {
-        if self.size.is_none() {
-            self.size = Some(size);
+        self.size = Some(size);
+    }
+    fn content_size(&self) -> Option<SizeInPixels2d> {
+        self.content_size
+    }
+    fn layout_state(&self) -> LayoutState {
+        self.layout_state
+    }
+    fn set_layout_state(&mut self, state: LayoutState) {
+        self.layout_state = state;
+    }
+    fn respond(&mut self, available: SizeInPixels2d) -> bool {
+        let changed = self.style.respond(available);
+        if changed {
+            self.layout_state.dirty = Dirty::Size;
+            if let Some(measure) = self.measure {
+                measure(self);
+            }
+        }
+        changed
+    }
+    fn limit_width(&mut self, width: Pixels) -> bool {
+        let limit = self.style.ellipsis.then_some(width);
+        if self.layout_state.width_limit == limit && self.layout_state.dirty < Dirty::Size {
+            return false;
+        }
+        self.layout_state.width_limit = limit;
+        let previous_size = self.content_size;
+        if let Some(measure) = self.measure {
+            measure(self);
         }
+        previous_size != self.content_size
+    }
+    fn kind(&self) -> &'static str {
+        describe_kind(&self.custom, self.describe)
+    }
+    fn text(&self) -> Option<&str> {
+        describe_text(&self.custom, self.describe)
     }
     fn style(&self) -> &Style {
         &self.style
@@ -106,48 +233,196 @@ impl<W: RenderableWidget<I> + 'static, I> From<W> for Box<dyn RenderableWidget<I
     }
 }
 
-pub fn compute_layout<I>(ui: &mut dyn WidgetTrait<I>, rect: Rect, layout: Layout) {
-    set_sizes(ui);
-    let anchor = Anchor::inside(rect, layout, vec2(0.0, 0.0));
-    set_positions(ui, anchor);
+/// Measures the whole tree, fits the root in `rect` according to its [`crate::lazy::Size`], distributes the
+/// space among the descendants and finally positions them. Returns the widgets that don't fit.
+pub fn compute_layout<I, L: Into<Layout>>(
+    ui: &mut dyn WidgetTrait<I>,
+    rect: Rect,
+    layout: L,
+) -> Vec<Overflow> {
+    layout::compute_layout(ui, rect, layout.into())
 }
 
+/// Widgets whose margined rect goes beyond the inner rect (inside the pad) of their parent, or
+/// beyond `rect` for the root. See [`crate::lazy::overflow`].
+pub fn find_overflows<I>(node: &mut dyn WidgetTrait<I>, rect: Rect) -> Vec<Overflow> {
+    layout::find_overflows(node, rect)
+}
+
+/// Call this every frame, or after changing the language with [`crate::i18n::set_language`].
+/// Returns whether the layout changed.
+pub fn relocalize<I, L: Into<Layout>>(
+    ui: &mut dyn RenderableWidget<I>,
+    rect: Rect,
+    layout: L,
+) -> bool {
+    let changed = ui.relocalize();
+    if changed {
+        compute_layout(ui, rect, layout);
+    }
+    changed
+}
+
+/// Returns whether any child changed its texts.
+pub fn relocalize_children<I>(children: &mut Widgets<I>) -> bool {
+    let mut changed = false;
+    for child in children {
+        changed |= child.relocalize();
+    }
+    changed
+}
+
+/// First pass of the layout: computes the natural size of every widget, bottom-up. Leaves take
+/// their [`WidgetTrait::content_size`], containers fit their children, and
+/// [`crate::lazy::Size::Fixed`] widgets take their fixed size.
 pub fn set_sizes<I>(node: &mut dyn WidgetTrait<I>) {
-    let mut accumulated_size = SizeInPixels2d::new(0.0, 0.0);
-    let style = node.style();
-    let parallel = style.layout.parallel_index();
-    let perpendicular = style.layout.perpendicular_index();
-    for child in node.children_mut() {
-        set_sizes(child);
-        let size = child.size();
-        let margin = child.style().margin.vec2();
-        accumulated_size[parallel] += size[parallel] + 2.0 * margin[parallel];
-        accumulated_size[perpendicular] =
-            accumulated_size[perpendicular].max(size[perpendicular] + 2.0 * margin[perpendicular]);
-    }
-    accumulated_size += 2.0 * node.style().pad.vec2();
-    node.set_size(accumulated_size);
-    // println!(
-    //     "size: {}, margin: {}, pad: {}",
-    //     node.node().size(),
-    //     node.node().style().margin.vec2(),
-    //     node.node().style().pad.vec2()
-    // );
+    set_sizes_within(node, SizeInPixels2d::splat(f32::INFINITY))
+}
+
+/// Like [`set_sizes`], but [`crate::lazy::Arrangement::Wrap`] containers break their lines so that they fit in
+/// `available`, which is the space for the margined `node`.
+pub fn set_sizes_within<I>(node: &mut dyn WidgetTrait<I>, available: SizeInPixels2d) {
+    layout::set_sizes_within(node, available)
+}
+
+/// Second pass of the layout, see [`layout::distribute_sizes`].
+pub fn distribute_sizes<I>(node: &mut dyn WidgetTrait<I>) {
+    layout::distribute_sizes(node)
 }
+
+/// Last pass of the layout, see [`layout::set_positions`].
 pub fn set_positions<I>(node: &mut dyn WidgetTrait<I>, outer_anchor: Anchor) -> Rect {
-    let margined_size = node.size() + node.style().margin.vec2() * 2.0;
-    let margined_pos = outer_anchor.get_top_left_pixel(margined_size);
-    let pos = margined_pos + node.style().margin.vec2();
-    node.set_pos(pos);
-
-    // the first child has to Anchor::inside, and the next ones need to Anchor::next_to, so create an empty rect as first child
-    let initial_anchor = Anchor::inside(node.rect(), node.style().layout, node.style().pad.vec2());
-    let zero2d = SizeInPixels2d::default();
-    let mut previous_rect = initial_anchor.get_rect(zero2d);
-    let style = node.style().clone();
-    for child in node.children_mut() {
-        let anchor = Anchor::next_to(previous_rect, style.layout, 0.0);
-        previous_rect = set_positions(child, anchor);
-    }
-    to_rect(margined_pos, margined_size)
+    layout::set_positions(node, outer_anchor)
+}
+
+/// One line per widget, see [`crate::lazy::dump`].
+pub fn dump<I>(node: &dyn WidgetTrait<I>) -> String {
+    dump_node(node)
+}
+
+/// The most that has to be computed again in the layout of `node` or any of its descendants.
+pub fn subtree_dirty<I>(node: &dyn WidgetTrait<I>) -> Dirty {
+    layout::subtree_dirty(node)
+}
+
+impl<'a, I> layout::LayoutNode for dyn WidgetTrait<I> + 'a {
+    type Style = Style;
+    type Child<'b>
+        = dyn WidgetTrait<I> + 'b
+    where
+        Self: 'b;
+
+    fn size(&self) -> SizeInPixels2d {
+        WidgetTrait::size(self)
+    }
+    fn pos(&self) -> PositionInPixels2d {
+        WidgetTrait::pos(self)
+    }
+    fn set_size(&mut self, size: SizeInPixels2d) {
+        WidgetTrait::set_size(self, size)
+    }
+    fn set_pos(&mut self, position: PositionInPixels2d) {
+        WidgetTrait::set_pos(self, position)
+    }
+    fn content_size(&self) -> Option<SizeInPixels2d> {
+        WidgetTrait::content_size(self)
+    }
+    fn content_offset(&self) -> SizeInPixels2d {
+        WidgetTrait::content_offset(self)
+    }
+    fn layout_state(&self) -> LayoutState {
+        WidgetTrait::layout_state(self)
+    }
+    fn set_layout_state(&mut self, state: LayoutState) {
+        WidgetTrait::set_layout_state(self, state)
+    }
+    fn respond(&mut self, available: SizeInPixels2d) -> bool {
+        WidgetTrait::respond(self, available)
+    }
+    fn limit_width(&mut self, width: Pixels) -> bool {
+        WidgetTrait::limit_width(self, width)
+    }
+    fn clips_children(&self) -> bool {
+        WidgetTrait::clips_children(self)
+    }
+    fn kind(&self) -> &'static str {
+        WidgetTrait::kind(self)
+    }
+    fn text(&self) -> Option<&str> {
+        WidgetTrait::text(self)
+    }
+    fn style(&self) -> &Style {
+        WidgetTrait::style(self)
+    }
+    fn children(&self) -> Vec<&Self::Child<'_>> {
+        WidgetTrait::children(self)
+            .into_iter()
+            .map(|child| child as &Self::Child<'_>)
+            .collect()
+    }
+    fn children_mut(&mut self) -> Vec<&mut Self::Child<'_>> {
+        WidgetTrait::children_mut(self)
+            .into_iter()
+            .map(|child| child as &mut Self::Child<'_>)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elm::button::Button;
+    use crate::elm::container::Container;
+    use crate::elm::text::Text;
+    use crate::i18n;
+    use crate::lazy::dump::assert_golden;
+    use crate::lazy::Size;
+    use crate::testing::mock_measure;
+
+    #[test]
+    fn test_dump() {
+        let style = Style::default();
+        let text = |s: &str| -> Box<dyn RenderableWidget<()>> {
+            Box::new(Text::new_generic(
+                style.clone(),
+                s.to_string(),
+                mock_measure,
+            ))
+        };
+        let button = Button::new(style.clone(), (), vec![text("some button")]);
+        let mut ui = Container::new_raw(style.clone(), vec![text("some text"), button]);
+        compute_layout(&mut ui, Rect::new(0.0, 0.0, 2000.0, 1000.0), style.layout);
+        assert_golden(
+            &dump(&ui),
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/tests/golden/elm_basic_layout.txt"
+            ),
+        );
+    }
+
+    #[test]
+    fn test_relocalize_keeps_the_layout_size() {
+        i18n::load_language_str("en", "play = Play").unwrap();
+        i18n::load_language_str("fr", "play = Jeux").unwrap();
+        i18n::set_language("en");
+        let grow = Style::from(crate::lazy::Style {
+            size: Size::Grow,
+            ..Default::default()
+        });
+        let text = Text::<()>::new_localized_generic(grow.clone(), "play".into(), mock_measure);
+        let mut ui = Container::new_raw(grow, vec![Box::new(text)]);
+        let rect = Rect::new(0.0, 0.0, 200.0, 100.0);
+        let layout = Style::default().layout;
+        compute_layout(&mut ui, rect, layout);
+        let stretched = Rect::new(25.0, 17.0, 150.0, 66.0);
+        assert_eq!(ui.children()[0].rect(), stretched);
+        assert!(!relocalize(&mut ui, rect, layout));
+
+        // same natural size, but measuring it again lost the grown size
+        i18n::set_language("fr");
+        assert!(relocalize(&mut ui, rect, layout));
+        assert!(dump(&ui).contains("Jeux"));
+        assert_eq!(ui.children()[0].rect(), stretched);
+    }
 }
@@ -0,0 +1,397 @@
+//! Registry of loaded fonts, so that styles can refer to them with a cheap [`FontId`].
+//!
+//! The registry is global (one per thread, and macroquad only renders from the main thread)
+//! because the render functions of the widgets only receive a style, and that style only has
+//! the [`FontId`]. Use [`load_ttf_bytes`] or [`load_ttf_file`] once at startup and store the
+//! returned ids in your styles.
+
+use crate::measure_cache;
+use crate::widgets::text::MeasureText;
+use macroquad::prelude::{Font, TextDimensions, TextParams};
+use macroquad::text::load_ttf_font_from_bytes;
+use macroquad::Error;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+
+pub type FontId = usize;
+
+/// macroquad's built-in font. It's always registered.
+pub const DEFAULT_FONT: FontId = 0;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum FontVariant {
+    Regular,
+    Bold,
+    Italic,
+    BoldItalic,
+}
+
+/// A set of fonts that share a name. Missing variants fall back to the closest available one,
+/// and ultimately to `regular`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct FontFamily {
+    pub regular: FontId,
+    pub bold: Option<FontId>,
+    pub italic: Option<FontId>,
+    pub bold_italic: Option<FontId>,
+}
+
+impl FontFamily {
+    pub fn new(regular: FontId) -> Self {
+        Self {
+            regular,
+            bold: None,
+            italic: None,
+            bold_italic: None,
+        }
+    }
+    pub fn get(&self, variant: FontVariant) -> FontId {
+        match variant {
+            FontVariant::Regular => self.regular,
+            FontVariant::Bold => self.bold.unwrap_or(self.regular),
+            FontVariant::Italic => self.italic.unwrap_or(self.regular),
+            FontVariant::BoldItalic => self
+                .bold_italic
+                .or(self.bold)
+                .or(self.italic)
+                .unwrap_or(self.regular),
+        }
+    }
+}
+
+/// An id that wasn't returned by the [`FontRegistry`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct UnknownFont(pub FontId);
+
+impl Display for UnknownFont {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "font {} is not registered", self.0)
+    }
+}
+
+struct RegisteredFont {
+    /// None means macroquad's default font
+    font: Option<Font>,
+    /// None means we don't know which glyphs it has, so we assume it has all of them
+    glyphs: Option<HashSet<char>>,
+    fallbacks: Vec<FontId>,
+}
+
+pub struct FontRegistry {
+    fonts: Vec<RegisteredFont>,
+    families: HashMap<String, FontFamily>,
+    paths: HashMap<String, FontId>,
+}
+
+impl Default for FontRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FontRegistry {
+    pub fn new() -> Self {
+        let default_font = RegisteredFont {
+            font: None,
+            glyphs: None,
+            fallbacks: Vec::new(),
+        };
+        Self {
+            fonts: vec![default_font],
+            families: HashMap::new(),
+            paths: HashMap::new(),
+        }
+    }
+
+    pub fn load_ttf_bytes(&mut self, bytes: &[u8]) -> Result<FontId, Error> {
+        let font = load_ttf_font_from_bytes(bytes)?;
+        Ok(self.register(Some(font), Some(read_glyphs(bytes)?)))
+    }
+
+    /// Registers an already loaded font. As we can't know which glyphs it has, it will never
+    /// delegate characters to its fallbacks.
+    pub fn add_font(&mut self, font: Font) -> FontId {
+        self.register(Some(font), None)
+    }
+
+    fn register(&mut self, font: Option<Font>, glyphs: Option<HashSet<char>>) -> FontId {
+        self.fonts.push(RegisteredFont {
+            font,
+            glyphs,
+            fallbacks: Vec::new(),
+        });
+        self.fonts.len() - 1
+    }
+
+    pub fn get(&self, id: FontId) -> Option<&Font> {
+        self.fonts
+            .get(id)
+            .and_then(|registered| registered.font.as_ref())
+    }
+
+    pub fn len(&self) -> usize {
+        self.fonts.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.fonts.is_empty()
+    }
+
+    pub fn add_family(&mut self, name: &str, family: FontFamily) {
+        self.families.insert(name.to_string(), family);
+    }
+    pub fn family(&self, name: &str) -> Option<&FontFamily> {
+        self.families.get(name)
+    }
+    pub fn family_font(&self, name: &str, variant: FontVariant) -> Option<FontId> {
+        self.family(name).map(|family| family.get(variant))
+    }
+
+    /// Characters that `font` doesn't have will be rendered with the first font in `fallbacks`
+    /// that has them. Nothing changes if any of the fonts is not registered.
+    pub fn set_fallbacks(
+        &mut self,
+        font: FontId,
+        fallbacks: Vec<FontId>,
+    ) -> Result<(), UnknownFont> {
+        if let Some(unknown) = fallbacks.iter().find(|fallback| **fallback >= self.len()) {
+            return Err(UnknownFont(*unknown));
+        }
+        let registered = self.fonts.get_mut(font).ok_or(UnknownFont(font))?;
+        registered.fallbacks = fallbacks;
+        Ok(())
+    }
+
+    pub fn has_glyph(&self, font: FontId, character: char) -> bool {
+        match self.fonts.get(font) {
+            Some(RegisteredFont {
+                glyphs: Some(glyphs),
+                ..
+            }) => glyphs.contains(&character),
+            Some(RegisteredFont { glyphs: None, .. }) => true,
+            None => false,
+        }
+    }
+
+    /// Splits the text in consecutive pieces that can be rendered with a single font each.
+    pub fn split_runs<'a>(&self, text: &'a str, font: FontId) -> Vec<(FontId, &'a str)> {
+        let fallbacks = self
+            .fonts
+            .get(font)
+            .map(|registered| registered.fallbacks.as_slice())
+            .unwrap_or_default();
+        let mut runs: Vec<(FontId, &'a str)> = Vec::new();
+        let mut run_start = 0;
+        let mut run_font = font;
+        for (index, character) in text.char_indices() {
+            let chosen = if self.has_glyph(font, character) {
+                font
+            } else {
+                fallbacks
+                    .iter()
+                    .copied()
+                    .find(|fallback| self.has_glyph(*fallback, character))
+                    .unwrap_or(font)
+            };
+            if chosen != run_font && index != run_start {
+                runs.push((run_font, &text[run_start..index]));
+                run_start = index;
+            }
+            run_font = chosen;
+        }
+        if run_start < text.len() {
+            runs.push((run_font, &text[run_start..]));
+        }
+        runs
+    }
+
+    /// Measures the text taking into account that some parts may be rendered with fallback fonts.
+    pub fn measure_text(
+        &self,
+        text: &str,
+        font: FontId,
+        font_size: u16,
+        font_scale: f32,
+        measure_text: MeasureText,
+    ) -> TextDimensions {
+        let runs = self.split_runs(text, font);
+        if runs.len() <= 1 {
+            return measure_text(text, self.get(font), font_size, font_scale);
+        }
+        let mut width = 0.0;
+        let mut above_baseline: f32 = 0.0;
+        let mut below_baseline: f32 = 0.0;
+        for (run_font, run) in runs {
+            let dimensions = measure_text(run, self.get(run_font), font_size, font_scale);
+            width += dimensions.width;
+            above_baseline = above_baseline.max(dimensions.offset_y);
+            below_baseline = below_baseline.max(dimensions.height - dimensions.offset_y);
+        }
+        TextDimensions {
+            width,
+            height: above_baseline + below_baseline,
+            offset_y: above_baseline,
+        }
+    }
+}
+
+thread_local! {
+    static FONTS: RefCell<FontRegistry> = RefCell::new(FontRegistry::new());
+}
+
+/// Gives access to the global registry. Don't call other functions of this module from `f`.
+pub fn with_fonts<R>(f: impl FnOnce(&mut FontRegistry) -> R) -> R {
+    FONTS.with(|fonts| f(&mut fonts.borrow_mut()))
+}
+
+pub fn load_ttf_bytes(bytes: &[u8]) -> Result<FontId, Error> {
+    with_fonts(|fonts| fonts.load_ttf_bytes(bytes))
+}
+
+/// Loading the same path several times only loads the file the first time.
+pub async fn load_ttf_file(path: &str) -> Result<FontId, Error> {
+    if let Some(id) = with_fonts(|fonts| fonts.paths.get(path).copied()) {
+        return Ok(id);
+    }
+    let bytes = macroquad::file::load_file(path).await?;
+    with_fonts(|fonts| {
+        let id = fonts.load_ttf_bytes(&bytes)?;
+        fonts.paths.insert(path.to_string(), id);
+        Ok(id)
+    })
+}
+
+pub fn add_font(font: Font) -> FontId {
+    with_fonts(|fonts| fonts.add_font(font))
+}
+
+pub fn get_font(id: FontId) -> Option<Font> {
+    with_fonts(|fonts| fonts.get(id).cloned())
+}
+
+pub fn add_family(name: &str, family: FontFamily) {
+    with_fonts(|fonts| fonts.add_family(name, family))
+}
+
+pub fn family_font(name: &str, variant: FontVariant) -> Option<FontId> {
+    with_fonts(|fonts| fonts.family_font(name, variant))
+}
+
+pub fn set_fallbacks(font: FontId, fallbacks: Vec<FontId>) -> Result<(), UnknownFont> {
+    with_fonts(|fonts| fonts.set_fallbacks(font, fallbacks))?;
+    measure_cache::clear();
+    Ok(())
+}
+
+pub fn measure_text(
+    text: &str,
+    font: FontId,
+    font_size: u16,
+    font_scale: f32,
+    measure_text: MeasureText,
+) -> TextDimensions {
+    with_fonts(|fonts| fonts.measure_text(text, font, font_size, font_scale, measure_text))
+}
+
+/// Like [`macroquad::text::draw_text_ex`], but characters missing in `font` are drawn with its
+/// fallbacks. `params.font` is ignored.
+pub fn draw_text_ex(text: &str, x: f32, y: f32, font: FontId, params: TextParams) {
+    let runs = with_fonts(|fonts| {
+        fonts
+            .split_runs(text, font)
+            .into_iter()
+            .map(|(run_font, run)| (fonts.get(run_font).cloned(), run))
+            .collect::<Vec<_>>()
+    });
+    let mut x = x;
+    for (run_font, run) in runs {
+        let run_params = TextParams {
+            font: run_font.as_ref(),
+            ..params.clone()
+        };
+        x += macroquad::text::draw_text_ex(run, x, y, run_params).width;
+    }
+}
+
+/// The characters that a TTF has, from its character map only, as macroquad already parses the
+/// whole font.
+fn read_glyphs(bytes: &[u8]) -> Result<HashSet<char>, Error> {
+    let face = ttf_parser::Face::parse(bytes, 0)
+        .map_err(|_| Error::FontError("can't read the character map of the font"))?;
+    let mut glyphs = HashSet::new();
+    if let Some(cmap) = face.tables().cmap {
+        for subtable in cmap.subtables.into_iter().filter(|s| s.is_unicode()) {
+            subtable.codepoints(|codepoint| {
+                let mapped = subtable
+                    .glyph_index(codepoint)
+                    .is_some_and(|glyph| glyph.0 != 0);
+                if let Some(character) = char::from_u32(codepoint).filter(|_| mapped) {
+                    glyphs.insert(character);
+                }
+            });
+        }
+    }
+    Ok(glyphs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry_with_roboto() -> (FontRegistry, FontId) {
+        let bytes = include_bytes!("../assets/Roboto-Regular.ttf");
+        let glyphs = read_glyphs(&bytes[..]).unwrap();
+        let mut registry = FontRegistry::new();
+        let roboto = registry.register(None, Some(glyphs));
+        (registry, roboto)
+    }
+
+    #[test]
+    fn test_runs_without_fallbacks() {
+        let (registry, roboto) = registry_with_roboto();
+        assert_eq!(
+            registry.split_runs("abc 漢字", roboto),
+            vec![(roboto, "abc 漢字")]
+        );
+        assert_eq!(registry.split_runs("", roboto), vec![]);
+    }
+
+    #[test]
+    fn test_runs_with_fallbacks() {
+        let (mut registry, roboto) = registry_with_roboto();
+        registry.set_fallbacks(roboto, vec![DEFAULT_FONT]).unwrap();
+        assert_eq!(
+            registry.split_runs("ab 漢字 c", roboto),
+            vec![(roboto, "ab "), (DEFAULT_FONT, "漢字"), (roboto, " c")]
+        );
+        assert_eq!(
+            registry.split_runs("漢a", roboto),
+            vec![(DEFAULT_FONT, "漢"), (roboto, "a")]
+        );
+    }
+
+    #[test]
+    fn test_unknown_fallbacks() {
+        let (mut registry, roboto) = registry_with_roboto();
+        let unknown = roboto + 1;
+        assert_eq!(
+            registry.set_fallbacks(unknown, vec![DEFAULT_FONT]),
+            Err(UnknownFont(unknown))
+        );
+        assert_eq!(
+            registry.set_fallbacks(roboto, vec![DEFAULT_FONT, unknown]),
+            Err(UnknownFont(unknown))
+        );
+        assert_eq!(registry.split_runs("漢a", roboto), vec![(roboto, "漢a")]);
+    }
+
+    #[test]
+    fn test_family_variants() {
+        let mut family = FontFamily::new(1);
+        family.bold = Some(2);
+        assert_eq!(family.get(FontVariant::Regular), 1);
+        assert_eq!(family.get(FontVariant::Bold), 2);
+        assert_eq!(family.get(FontVariant::Italic), 1);
+        assert_eq!(family.get(FontVariant::BoldItalic), 2);
+    }
+}
@@ -0,0 +1,279 @@
+//! Translation tables, so that widgets can hold a [`Localized`] key instead of a literal text.
+//!
+//! The tables are plain text files with one `key = value` per line. Empty lines and lines
+//! starting with `#` are ignored, and `\n` in a value is replaced by a line break. Values can
+//! have parameters like `{name}`, which are substituted with [`Localized::with_param`]:
+//! ```text
+//! # en.txt
+//! menu.play = Play
+//! menu.greeting = Hello, {name}!
+//! ```
+//!
+//! Like [`crate::fonts`], the tables are global (per thread). Every time the language or a table
+//! changes, [`revision`] changes, which is how widgets know they need to translate and measure
+//! their texts again. See `relocalize` in [`crate::widgets::text::TextRect`],
+//! [`crate::widgets::button_group::LabelGroup`] and [`crate::elm::widget::relocalize`].
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+pub type Language = String;
+pub type Table = HashMap<String, String>;
+
+#[derive(Debug)]
+pub enum I18nError {
+    File(macroquad::Error),
+    /// `line` starts at 1
+    Parse {
+        line: usize,
+        content: String,
+    },
+}
+
+impl Display for I18nError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            I18nError::File(error) => write!(f, "can't load translations: {}", error),
+            I18nError::Parse { line, content } => {
+                write!(f, "expected 'key = value' in line {}: {}", line, content)
+            }
+        }
+    }
+}
+
+impl From<macroquad::Error> for I18nError {
+    fn from(error: macroquad::Error) -> Self {
+        I18nError::File(error)
+    }
+}
+
+pub fn parse_table(contents: &str) -> Result<Table, I18nError> {
+    let mut table = Table::new();
+    for (index, line) in contents.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if let Some((key, value)) = trimmed.split_once('=') {
+            let value = value.trim().replace("\\n", "\n");
+            table.insert(key.trim().to_string(), value);
+        } else {
+            return Err(I18nError::Parse {
+                line: index + 1,
+                content: line.to_string(),
+            });
+        }
+    }
+    Ok(table)
+}
+
+/// A translation key and the values of its parameters.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Localized {
+    pub key: String,
+    pub params: Vec<(String, String)>,
+}
+
+impl Localized {
+    pub fn new<S: Into<String>>(key: S) -> Self {
+        Self {
+            key: key.into(),
+            params: Vec::new(),
+        }
+    }
+    #[must_use]
+    pub fn with_param<N: Into<String>, V: ToString>(mut self, name: N, value: V) -> Self {
+        self.params.push((name.into(), value.to_string()));
+        self
+    }
+    /// Translates with the current language.
+    pub fn resolve(&self) -> String {
+        with_translations(|translations| translations.resolve(self))
+    }
+}
+
+impl From<&str> for Localized {
+    fn from(key: &str) -> Self {
+        Localized::new(key)
+    }
+}
+
+pub struct Translations {
+    tables: HashMap<Language, Table>,
+    language: Language,
+    fallback: Language,
+    revision: u64,
+}
+
+impl Default for Translations {
+    fn default() -> Self {
+        Self::new("en")
+    }
+}
+
+impl Translations {
+    pub fn new(language: &str) -> Self {
+        Self {
+            tables: HashMap::new(),
+            language: language.to_string(),
+            fallback: language.to_string(),
+            revision: 0,
+        }
+    }
+    pub fn add_table(&mut self, language: &str, table: Table) {
+        self.tables
+            .entry(language.to_string())
+            .or_default()
+            .extend(table);
+        self.revision += 1;
+    }
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+    pub fn set_language(&mut self, language: &str) {
+        if self.language != language {
+            self.language = language.to_string();
+            self.revision += 1;
+        }
+    }
+    pub fn fallback_language(&self) -> &str {
+        &self.fallback
+    }
+    /// Used for the keys that are missing in the current language.
+    pub fn set_fallback_language(&mut self, language: &str) {
+        if self.fallback != language {
+            self.fallback = language.to_string();
+            self.revision += 1;
+        }
+    }
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// If neither the current language nor the fallback language have the key, the key itself
+    /// is returned, so that the missing translation is visible.
+    pub fn translate<'a>(&'a self, key: &'a str) -> &'a str {
+        [&self.language, &self.fallback]
+            .into_iter()
+            .find_map(|language| self.tables.get(language)?.get(key))
+            .map(|value| value.as_str())
+            .unwrap_or(key)
+    }
+    /// Translates and substitutes the parameters in a single pass, so a value that contains
+    /// something like `{name}` is kept as is. Unknown parameters are left in the text.
+    pub fn resolve(&self, localized: &Localized) -> String {
+        let mut rest = self.translate(&localized.key);
+        let mut text = String::with_capacity(rest.len());
+        while let Some(open) = rest.find('{') {
+            text.push_str(&rest[..open]);
+            rest = &rest[open..];
+            let value = rest.find('}').and_then(|close| {
+                let name = &rest[1..close];
+                let (_, value) = localized.params.iter().find(|(n, _)| n == name)?;
+                Some((close, value))
+            });
+            if let Some((close, value)) = value {
+                text.push_str(value);
+                rest = &rest[close + 1..];
+            } else {
+                text.push('{');
+                rest = &rest[1..];
+            }
+        }
+        text.push_str(rest);
+        text
+    }
+}
+
+thread_local! {
+    static TRANSLATIONS: RefCell<Translations> = RefCell::new(Translations::default());
+}
+
+/// Gives access to the global translations. Don't call other functions of this module from `f`.
+pub fn with_translations<R>(f: impl FnOnce(&mut Translations) -> R) -> R {
+    TRANSLATIONS.with(|translations| f(&mut translations.borrow_mut()))
+}
+
+pub fn load_language_str(language: &str, contents: &str) -> Result<(), I18nError> {
+    let table = parse_table(contents)?;
+    with_translations(|translations| translations.add_table(language, table));
+    Ok(())
+}
+
+pub async fn load_language_file(language: &str, path: &str) -> Result<(), I18nError> {
+    let contents = macroquad::file::load_string(path).await?;
+    load_language_str(language, &contents)
+}
+
+pub fn set_language(language: &str) {
+    with_translations(|translations| translations.set_language(language))
+}
+
+pub fn set_fallback_language(language: &str) {
+    with_translations(|translations| translations.set_fallback_language(language))
+}
+
+pub fn language() -> Language {
+    with_translations(|translations| translations.language().to_string())
+}
+
+pub fn revision() -> u64 {
+    with_translations(|translations| translations.revision())
+}
+
+pub fn tr(key: &str) -> String {
+    with_translations(|translations| translations.translate(key).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translations() -> Translations {
+        let mut translations = Translations::new("en");
+        let en = "# comment\n\
+            greeting = Hello, {name}!\n\
+            \n\
+            exit = Exit\n\
+            multiline = first\\nsecond";
+        translations.add_table("en", parse_table(en).unwrap());
+        translations.add_table("es", parse_table("greeting = ¡Hola, {name}!").unwrap());
+        translations
+    }
+
+    #[test]
+    fn test_translate_with_fallback() {
+        let mut translations = translations();
+        let greeting = Localized::new("greeting").with_param("name", "Ferris");
+        assert_eq!(translations.resolve(&greeting), "Hello, Ferris!");
+        assert_eq!(translations.translate("multiline"), "first\nsecond");
+
+        let revision = translations.revision();
+        translations.set_language("es");
+        assert_ne!(translations.revision(), revision);
+        assert_eq!(translations.resolve(&greeting), "¡Hola, Ferris!");
+        assert_eq!(translations.translate("exit"), "Exit");
+        assert_eq!(translations.translate("missing"), "missing");
+    }
+
+    #[test]
+    fn test_params_are_substituted_once() {
+        let mut translations = Translations::new("en");
+        let table = parse_table("pair = {first} and {second}, not {third}").unwrap();
+        translations.add_table("en", table);
+        let pair = Localized::new("pair")
+            .with_param("first", "{second}")
+            .with_param("second", "{first}");
+        assert_eq!(
+            translations.resolve(&pair),
+            "{second} and {first}, not {third}"
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let result = parse_table("a = b\nno equals sign");
+        assert!(matches!(result, Err(I18nError::Parse { line: 2, .. })));
+    }
+}
@@ -1,9 +1,10 @@
 use crate::draw::draw_rect;
 use crate::input::input_macroquad::InputMacroquad;
 use crate::input::input_trait::InputTrait;
+use crate::lazy::dump::{describe, Describe};
 use crate::lazy::{
-    draw_debug_widget, Interactable, Renderable, Style, WidgetData, WidgetTrait, Widgets,
-    DEBUG_WIDGETS,
+    draw_debug_widget, Interactable, LayoutState, Renderable, Style, WidgetData, WidgetTrait,
+    Widgets, DEBUG_WIDGETS,
 };
 use crate::widgets::button::draw_panel_border;
 use crate::widgets::{interact, Interaction};
@@ -17,6 +18,12 @@ pub struct ButtonBase {
     pub input: Box<dyn InputTrait>,
     pub render_button: RenderButton,
 }
+
+impl Describe for ButtonBase {
+    fn kind(&self) -> &'static str {
+        "Button"
+    }
+}
 impl Default for ButtonBase {
     fn default() -> Self {
         Self {
@@ -43,6 +50,10 @@ impl Button {
         Self {
             pos: Default::default(),
             size: None,
+            content_size: None,
+            layout_state: LayoutState::default(),
+            measure: None,
+            describe: Some(describe),
             style,
             custom,
             children,
@@ -76,6 +87,8 @@ fn render_interactive(widget: &Button, _unused: Interaction) {
         draw_debug_widget(widget);
     }
     for child in widget.children() {
-        child.render_interactive(widget.custom.interaction);
+        if child.style().visible {
+            child.render_interactive(widget.custom.interaction);
+        }
     }
 }
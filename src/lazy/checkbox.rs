@@ -0,0 +1,181 @@
+use crate::input::input_macroquad::InputMacroquad;
+use crate::input::input_trait::InputTrait;
+use crate::lazy::dump::{describe, Describe};
+use crate::lazy::text::Text;
+use crate::lazy::{
+    draw_debug_widget, Interactable, Margin, Pad, Renderable, Style, WidgetData, WidgetTrait,
+    Widgets, DEBUG_WIDGETS,
+};
+use crate::widgets::checkbox::{
+    draw_indicator, indicator_rect, indicator_start, widen_pads, ToggleKind,
+};
+use crate::widgets::{interact, Interaction};
+use std::any::Any;
+
+/// A checkbox or a toggle switch, see [`crate::widgets::checkbox`]. The children are the label,
+/// and the indicator goes in the left pad, which is widened to have the indicator with the pad at
+/// each side of it.
+pub type Checkbox = WidgetData<CheckboxBase>;
+pub type RenderCheckbox = fn(widget: &Checkbox, interaction: Interaction);
+
+pub struct CheckboxBase {
+    pub checked: bool,
+    pub kind: ToggleKind,
+    pub interaction: Interaction,
+    pub input: Box<dyn InputTrait>,
+    pub render_checkbox: RenderCheckbox,
+}
+
+impl Describe for CheckboxBase {
+    fn kind(&self) -> &'static str {
+        self.kind.name()
+    }
+}
+
+impl Checkbox {
+    pub fn new(style: Style, text: &str, checked: bool) -> Self {
+        Self::new_text(style, text, checked, ToggleKind::Checkbox)
+    }
+    pub fn new_switch(style: Style, text: &str, checked: bool) -> Self {
+        Self::new_text(style, text, checked, ToggleKind::Switch)
+    }
+    fn new_text(style: Style, text: &str, checked: bool, kind: ToggleKind) -> Self {
+        let mut text_style = style.clone();
+        // the checkbox pads the label
+        text_style.margin = Margin::new_symmetric(0.0);
+        text_style.pad = Pad::new_symmetric(0.0);
+        for breakpoint in &mut text_style.breakpoints {
+            breakpoint.variant.pad = text_style.pad;
+        }
+        let label = Box::new(Text::new(&text_style, text));
+        Self::new_generic(
+            style,
+            checked,
+            kind,
+            Box::new(InputMacroquad),
+            render_checkbox,
+            vec![label],
+        )
+    }
+    pub fn new_generic(
+        mut style: Style,
+        checked: bool,
+        kind: ToggleKind,
+        input: Box<dyn InputTrait>,
+        render_checkbox: RenderCheckbox,
+        children: Widgets,
+    ) -> Self {
+        widen_pads(
+            kind,
+            &mut style.pad,
+            style.font_size,
+            &mut style.breakpoints,
+        );
+        let custom = CheckboxBase {
+            checked,
+            kind,
+            interaction: Interaction::None,
+            input,
+            render_checkbox,
+        };
+        let mut checkbox = Self::container_custom(style, custom, children);
+        checkbox.describe = Some(describe);
+        checkbox
+    }
+    /// Flips the value if clicked.
+    pub fn interact(&mut self) -> Interaction {
+        self.custom.interaction = interact(self.rect(), &self.custom.input);
+        if self.custom.interaction.is_clicked() {
+            self.custom.checked = !self.custom.checked;
+        }
+        self.custom.interaction
+    }
+    pub fn checked(&self) -> bool {
+        self.custom.checked
+    }
+}
+
+impl Renderable for Checkbox {
+    fn render_interactive(&self, interaction: Interaction) {
+        (self.custom.render_checkbox)(self, interaction)
+    }
+}
+impl Interactable for Checkbox {
+    /// Returns the current value, like [`crate::lazy::slider::Slider`].
+    fn interact(&mut self) -> Vec<Box<dyn Any>> {
+        self.interact();
+        vec![Box::new(self.custom.checked)]
+    }
+}
+
+fn render_checkbox(widget: &Checkbox, _unused: Interaction) {
+    let style = widget.style();
+    let kind = widget.custom.kind;
+    let font_size = style.font_size;
+    let start = indicator_start(kind, style.pad.left, font_size);
+    let rect = indicator_rect(kind, widget.rect(), start, font_size);
+    let state_style = style.coloring.choose(widget.custom.interaction);
+    draw_indicator(kind, rect, widget.custom.checked, state_style);
+    if unsafe { DEBUG_WIDGETS } {
+        draw_debug_widget(widget);
+    }
+    for child in widget.children() {
+        if child.style().visible {
+            child.render_interactive(widget.custom.interaction);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lazy::compute_layout;
+    use crate::lazy::responsive::Breakpoint;
+    use crate::lazy::text::render_text;
+    use crate::testing::{mock_measure, MockInput};
+    use crate::widgets::anchor::{Horizontal, Layout, Vertical};
+    use crate::widgets::checkbox::indicator_pad;
+    use macroquad::math::{vec2, Rect};
+
+    #[test]
+    fn test_checkbox() {
+        let input = MockInput::default();
+        let label_style = Style {
+            pad: Pad::new_symmetric(0.0),
+            ..Style::default()
+        };
+        let label = Text::new_generic(&label_style, "label", mock_measure, render_text);
+        let mut style = Style::default();
+        let mut wide = style.variant();
+        wide.pad = Pad::new_symmetric(4.0);
+        style.breakpoints = vec![Breakpoint::min_width(100.0, wide)];
+        let mut checkbox = Checkbox::new_generic(
+            style.clone(),
+            false,
+            ToggleKind::Checkbox,
+            Box::new(Clone::clone(&input)),
+            render_checkbox,
+            vec![Box::new(label)],
+        );
+        let top_left = Layout::horizontal(Horizontal::Right, Vertical::Top);
+        compute_layout(&mut checkbox, Rect::new(0.0, 0.0, 50.0, 500.0), top_left);
+        let left = indicator_pad(ToggleKind::Checkbox, style.pad.left, style.font_size);
+        assert_eq!(left, 2.0 * style.pad.left + 16.0);
+        assert_eq!(checkbox.style().pad.left, left);
+        let label_size = vec2(60.0, 16.0);
+        assert_eq!(checkbox.children()[0].size(), label_size);
+        let extra = vec2(left - style.pad.left, 0.0);
+        assert_eq!(checkbox.size(), style.pad.total() + extra + label_size);
+
+        compute_layout(&mut checkbox, Rect::new(0.0, 0.0, 500.0, 500.0), top_left);
+        assert_eq!(checkbox.style().pad.left, 2.0 * 4.0 + 16.0);
+        assert_eq!(checkbox.children()[0].rect().x, 2.0 * 4.0 + 16.0);
+
+        assert!(!checkbox.interact().is_clicked());
+        input.click(checkbox.rect().center());
+        assert!(checkbox.interact().is_clicked());
+        assert!(checkbox.checked());
+        assert!(checkbox.interact().is_clicked());
+        assert!(!checkbox.checked());
+    }
+}
@@ -0,0 +1,204 @@
+//! A stable text form of a laid out tree, one line per widget, for tests and debugging. Shared by
+//! the `lazy` ([`dump`]) and `elm` ([`crate::elm::widget::dump`]) trees. For example:
+//!
+//! ```text
+//! Panel rect=[862 420 276 160] pad=[16 24 16 24] margin=[0 0 0 0]
+//!   Text "some text" rect=[922 436 156 48] pad=[16 24 16 24] margin=[0 0 0 0]
+//! ```
+//!
+//! Sides go clockwise from the top, like in [`Pad::sides`]. Hidden widgets end with `hidden`.
+
+use crate::lazy::layout::{LayoutNode, LayoutStyle};
+use crate::lazy::{Pad, WidgetTrait};
+use macroquad::math::Rect;
+use std::path::Path;
+
+/// What a [`crate::lazy::WidgetData`] or a [`crate::elm::widget::Widget`] tells about itself in
+/// a dump, depending on its custom part. It's optional: see [`DescribeFn`].
+pub trait Describe {
+    fn kind(&self) -> &'static str;
+    /// The text shown by the widget itself, not by its children.
+    fn text(&self) -> Option<&str> {
+        None
+    }
+}
+
+impl Describe for () {
+    fn kind(&self) -> &'static str {
+        "Container"
+    }
+}
+
+/// Gives the [`Describe`] impl of the custom part of a widget, if it has one. Custom parts
+/// without it are named after their type, see [`kind_of`].
+pub type DescribeFn<C> = fn(&C) -> &dyn Describe;
+
+/// The [`DescribeFn`] of custom parts that implement [`Describe`].
+pub fn describe<C: Describe>(custom: &C) -> &dyn Describe {
+    custom
+}
+
+/// The name of the type `C` without its path, generics and "Base" suffix, e.g. "Panel" for
+/// `PanelBase<()>`.
+pub fn kind_of<C>() -> &'static str {
+    let name = std::any::type_name::<C>();
+    let name = name.split('<').next().unwrap_or(name);
+    let name = name.rsplit("::").next().unwrap_or(name);
+    name.strip_suffix("Base").unwrap_or(name)
+}
+
+/// [`Describe::kind`] of `custom`, or [`kind_of`] its type.
+pub fn describe_kind<C>(custom: &C, describe: Option<DescribeFn<C>>) -> &'static str {
+    match describe {
+        Some(describe) => describe(custom).kind(),
+        None => kind_of::<C>(),
+    }
+}
+
+/// [`Describe::text`] of `custom`, if it implements [`Describe`].
+pub fn describe_text<C>(custom: &C, describe: Option<DescribeFn<C>>) -> Option<&str> {
+    describe.and_then(|describe| describe(custom).text())
+}
+
+pub fn dump(node: &dyn WidgetTrait) -> String {
+    dump_node(node)
+}
+
+/// [`dump`] for any tree that implements [`LayoutNode`].
+pub fn dump_node<N: LayoutNode + ?Sized>(node: &N) -> String {
+    let mut lines = String::new();
+    dump_into(node, 0, &mut lines);
+    lines
+}
+
+fn dump_into<N: LayoutNode + ?Sized>(node: &N, depth: usize, lines: &mut String) {
+    let style = node.style();
+    lines.push_str(&dump_line(
+        depth,
+        node.kind(),
+        node.text(),
+        node.rect(),
+        style.pad(),
+        style.margin(),
+        style.visible(),
+    ));
+    for child in node.children() {
+        dump_into(child, depth + 1, lines);
+    }
+}
+
+/// One line of a dump, newline included.
+pub fn dump_line(
+    depth: usize,
+    kind: &str,
+    text: Option<&str>,
+    rect: Rect,
+    pad: Pad,
+    margin: Pad,
+    visible: bool,
+) -> String {
+    let mut line = format!("{}{}", "  ".repeat(depth), kind);
+    if let Some(text) = text {
+        line += &format!(" {:?}", text);
+    }
+    line += &format!(
+        " rect=[{} {} {} {}] pad={} margin={}",
+        rect.x,
+        rect.y,
+        rect.w,
+        rect.h,
+        sides(pad),
+        sides(margin)
+    );
+    if !visible {
+        line += " hidden";
+    }
+    line + "\n"
+}
+
+fn sides(pad: Pad) -> String {
+    format!("[{} {} {} {}]", pad.top, pad.right, pad.bottom, pad.left)
+}
+
+/// Panics with a line diff if `actual` is not the content of the file at `path`. If the
+/// `UPDATE_GOLDEN` environment variable is set, writes `actual` to the file instead.
+pub fn assert_golden<P: AsRef<Path>>(actual: &str, path: P) {
+    let path = path.as_ref();
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::write(path, actual)
+            .unwrap_or_else(|error| panic!("can't write {}: {}", path.display(), error));
+        return;
+    }
+    let expected = std::fs::read_to_string(path).unwrap_or_else(|error| {
+        panic!(
+            "can't read {} (run with UPDATE_GOLDEN=1 to create it): {}",
+            path.display(),
+            error
+        )
+    });
+    if expected != actual {
+        panic!(
+            "{} doesn't match (run with UPDATE_GOLDEN=1 to update it):\n{}",
+            path.display(),
+            line_diff(&expected, actual)
+        );
+    }
+}
+
+/// The lines of `expected` and `actual`, prefixed with `-` if only in `expected`, `+` if only in
+/// `actual`, and a space if in both.
+pub fn line_diff(expected: &str, actual: &str) -> String {
+    let old = expected.lines().collect::<Vec<_>>();
+    let new = actual.lines().collect::<Vec<_>>();
+    // common[i][j] is the length of the longest common subsequence of old[i..] and new[j..]
+    let mut common = vec![vec![0; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+    let mut diff = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            diff += &format!("  {}\n", old[i]);
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || common[i + 1][j] >= common[i][j + 1]) {
+            diff += &format!("- {}\n", old[i]);
+            i += 1;
+        } else {
+            diff += &format!("+ {}\n", new[j]);
+            j += 1;
+        }
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lazy::{Style, WidgetData};
+
+    #[test]
+    fn test_line_diff() {
+        let expected = "a\nb\nc\n";
+        let actual = "a\nx\nc\nd\n";
+        assert_eq!(line_diff(expected, actual), "  a\n- b\n+ x\n  c\n+ d\n");
+        assert_eq!(line_diff(expected, expected), "  a\n  b\n  c\n");
+    }
+
+    #[test]
+    fn test_undescribed_custom() {
+        struct GaugeBase<T>(T);
+        let gauge = WidgetData::leaf_custom(Style::default(), GaugeBase(0.5));
+        assert_eq!(gauge.custom.0, 0.5);
+        assert_eq!(gauge.kind(), "Gauge");
+        assert_eq!(gauge.text(), None);
+        assert_eq!(kind_of::<()>(), "()");
+    }
+}
@@ -0,0 +1,295 @@
+//! Distribution of the space of a container among its children along the layout direction,
+//! shared by the `lazy` and `elm` layout passes.
+
+use crate::lazy::{resolve_size, Margin, Size};
+use crate::widgets::anchor::Layout;
+use crate::widgets::text::Pixels;
+use crate::SizeInPixels2d;
+use std::ops::Range;
+
+/// Where the children go in the layout direction when they don't use all the space of the
+/// container. `Start` is the side where the layout begins, e.g. the top for
+/// `Layout::Vertical { direction: Vertical::Bottom, .. }`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Default)]
+pub enum Justify {
+    #[default]
+    Start,
+    Center,
+    End,
+    /// no space before the first child nor after the last one
+    SpaceBetween,
+    /// each child has the same space before and after it
+    SpaceAround,
+    /// same space between children and at both ends
+    SpaceEvenly,
+}
+
+impl Justify {
+    /// Returns the space before the first child and the space between consecutive children,
+    /// given the `free` space left after placing `count` children separated by `gap`.
+    pub fn spacing(&self, free: Pixels, gap: Pixels, count: usize) -> (Pixels, Pixels) {
+        let free = free.max(0.0);
+        let n = count as f32;
+        match self {
+            _ if count == 0 => (0.0, gap),
+            Justify::Start => (0.0, gap),
+            Justify::Center => (free * 0.5, gap),
+            Justify::End => (free, gap),
+            Justify::SpaceBetween if count == 1 => (0.0, gap),
+            Justify::SpaceBetween => (0.0, gap + free / (n - 1.0)),
+            Justify::SpaceAround => (free / (2.0 * n), gap + free / n),
+            Justify::SpaceEvenly => (free / (n + 1.0), gap + free / (n + 1.0)),
+        }
+    }
+}
+
+/// What [`flex_sizes`] needs to know about each child.
+#[derive(Copy, Clone)]
+pub struct FlexItem {
+    pub size: Size,
+    /// as computed by the first layout pass
+    pub natural: SizeInPixels2d,
+    pub margin: Margin,
+    pub grow: f32,
+    pub shrink: f32,
+    pub min_size: SizeInPixels2d,
+    pub max_size: SizeInPixels2d,
+}
+
+/// Final sizes of the children of a container with `inner` space inside its pad.
+///
+/// [`Size::Grow`] children fill the perpendicular axis, and share the space left by their
+/// siblings in the parallel axis proportionally to their `grow` weights. If the children don't
+/// fit, they give up space proportionally to `shrink * size`. Children never go beyond their
+/// `min_size` and `max_size`, and the space they can't take or give is shared by the rest.
+pub fn flex_sizes(
+    items: &[FlexItem],
+    inner: SizeInPixels2d,
+    layout: Layout,
+    gap: Pixels,
+) -> Vec<SizeInPixels2d> {
+    let parallel = layout.parallel_index();
+    let grows = |item: &FlexItem| matches!(item.size, Size::Grow);
+    let mut sizes = items
+        .iter()
+        .map(|item| {
+            let size = resolve_size(item.size, item.natural, inner, item.margin);
+            clamp_size(size, item.min_size, item.max_size)
+        })
+        .collect::<Vec<_>>();
+    let margins_and_gaps = gap * items.len().saturating_sub(1) as f32
+        + items
+            .iter()
+            .map(|item| item.margin.total()[parallel])
+            .sum::<f32>();
+
+    for (item, size) in items.iter().zip(&mut sizes) {
+        if grows(item) {
+            size[parallel] = clamp_length(item.natural[parallel], item, parallel);
+        }
+    }
+    let available = inner[parallel] - margins_and_gaps;
+    grow_items(items, &mut sizes, parallel, available);
+    shrink_items(items, &mut sizes, parallel, available);
+    sizes
+}
+
+/// The growing children that get clamped keep that size, and the rest share again the space.
+fn grow_items(items: &[FlexItem], sizes: &mut [SizeInPixels2d], parallel: usize, available: f32) {
+    let mut frozen = items
+        .iter()
+        .map(|item| !matches!(item.size, Size::Grow) || item.grow <= 0.0)
+        .collect::<Vec<_>>();
+    loop {
+        let (mut taken, mut total_grow) = (0.0, 0.0);
+        for (i, item) in items.iter().enumerate() {
+            if frozen[i] {
+                taken += sizes[i][parallel];
+            } else {
+                total_grow += item.grow;
+            }
+        }
+        if total_grow <= 0.0 {
+            return;
+        }
+        let per_weight = (available - taken) / total_grow;
+        let mut clamped_any = false;
+        for (i, item) in items.iter().enumerate() {
+            if !frozen[i] {
+                let target = item.natural[parallel].max(per_weight * item.grow);
+                let clamped = clamp_length(target, item, parallel);
+                sizes[i][parallel] = clamped;
+                if clamped != target {
+                    frozen[i] = true;
+                    clamped_any = true;
+                }
+            }
+        }
+        if !clamped_any {
+            return;
+        }
+    }
+}
+
+/// The shrinking children that get clamped keep that size, and the rest share again the overflow.
+fn shrink_items(items: &[FlexItem], sizes: &mut [SizeInPixels2d], parallel: usize, available: f32) {
+    let bases = sizes.iter().map(|size| size[parallel]).collect::<Vec<_>>();
+    let mut frozen = items
+        .iter()
+        .zip(&bases)
+        .map(|(item, base)| item.shrink <= 0.0 || *base <= 0.0)
+        .collect::<Vec<_>>();
+    loop {
+        let (mut used, mut total_shrink) = (0.0, 0.0);
+        for (i, item) in items.iter().enumerate() {
+            if frozen[i] {
+                used += sizes[i][parallel];
+            } else {
+                used += bases[i];
+                total_shrink += item.shrink * bases[i];
+            }
+        }
+        let overflow = used - available;
+        if overflow <= 0.0 || total_shrink <= 0.0 {
+            return;
+        }
+        let mut clamped_any = false;
+        for (i, item) in items.iter().enumerate() {
+            if !frozen[i] {
+                let target = bases[i] - overflow * item.shrink * bases[i] / total_shrink;
+                let clamped = clamp_length(target, item, parallel).max(0.0);
+                sizes[i][parallel] = clamped;
+                if clamped != target {
+                    frozen[i] = true;
+                    clamped_any = true;
+                }
+            }
+        }
+        if !clamped_any {
+            return;
+        }
+    }
+}
+
+fn clamp_length(length: Pixels, item: &FlexItem, axis: usize) -> Pixels {
+    length.min(item.max_size[axis]).max(item.min_size[axis])
+}
+
+/// `min_size` wins if it's bigger than `max_size`.
+pub fn clamp_size(
+    size: SizeInPixels2d,
+    min_size: SizeInPixels2d,
+    max_size: SizeInPixels2d,
+) -> SizeInPixels2d {
+    size.min(max_size).max(min_size)
+}
+
+/// Splits the children in lines for `Arrangement::Wrap`: a child goes to a new line if it doesn't
+/// fit in `available` after the children already in the line. A line has at least one child.
+pub fn wrap_lines(lengths: &[Pixels], available: Pixels, gap: Pixels) -> Vec<Range<usize>> {
+    let mut lines = Vec::new();
+    let mut start = 0;
+    let mut used = 0.0;
+    for (i, length) in lengths.iter().enumerate() {
+        if i == start {
+            used = *length;
+        } else if used + gap + length > available {
+            lines.push(start..i);
+            start = i;
+            used = *length;
+        } else {
+            used += gap + length;
+        }
+    }
+    if start < lengths.len() {
+        lines.push(start..lengths.len());
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::anchor::{Horizontal, Vertical};
+    use macroquad::math::vec2;
+
+    fn item(size: Size, natural: f32, grow: f32, shrink: f32) -> FlexItem {
+        FlexItem {
+            size,
+            natural: vec2(natural, 10.0),
+            margin: Margin::new_symmetric(0.0),
+            grow,
+            shrink,
+            min_size: SizeInPixels2d::ZERO,
+            max_size: SizeInPixels2d::splat(f32::INFINITY),
+        }
+    }
+
+    #[test]
+    fn test_grow_weights_and_gap() {
+        let layout = Layout::horizontal(Horizontal::Right, Vertical::Center);
+        let items = [
+            item(Size::Fit, 40.0, 1.0, 0.0),
+            item(Size::Grow, 0.0, 1.0, 0.0),
+            item(Size::Grow, 0.0, 3.0, 0.0),
+        ];
+        let sizes = flex_sizes(&items, vec2(200.0, 50.0), layout, 10.0);
+        assert_eq!(
+            sizes,
+            vec![vec2(40.0, 10.0), vec2(35.0, 50.0), vec2(105.0, 50.0)]
+        );
+    }
+
+    #[test]
+    fn test_shrink() {
+        let layout = Layout::horizontal(Horizontal::Right, Vertical::Center);
+        let items = [
+            item(Size::Fit, 100.0, 1.0, 1.0),
+            item(Size::Fit, 100.0, 1.0, 0.0),
+            item(Size::Fit, 50.0, 1.0, 2.0),
+        ];
+        let sizes = flex_sizes(&items, vec2(150.0, 50.0), layout, 0.0);
+        let widths = sizes.iter().map(|size| size.x).collect::<Vec<_>>();
+        assert_eq!(widths, vec![50.0, 100.0, 0.0]);
+    }
+
+    #[test]
+    fn test_min_and_max_sizes() {
+        let layout = Layout::horizontal(Horizontal::Right, Vertical::Center);
+        let mut limited = item(Size::Grow, 0.0, 1.0, 0.0);
+        limited.max_size.x = 30.0;
+        let items = [limited, item(Size::Grow, 0.0, 1.0, 0.0)];
+        let sizes = flex_sizes(&items, vec2(200.0, 50.0), layout, 0.0);
+        assert_eq!(sizes[0].x, 30.0);
+        assert_eq!(sizes[1].x, 170.0);
+
+        let mut touchable = item(Size::Fit, 100.0, 1.0, 1.0);
+        touchable.min_size.x = 80.0;
+        let items = [touchable, item(Size::Fit, 100.0, 1.0, 1.0)];
+        let sizes = flex_sizes(&items, vec2(150.0, 50.0), layout, 0.0);
+        assert_eq!(sizes[0].x, 80.0);
+        assert_eq!(sizes[1].x, 70.0);
+    }
+
+    #[test]
+    fn test_wrap_lines() {
+        let lengths = [30.0, 30.0, 50.0, 120.0, 10.0];
+        assert_eq!(
+            wrap_lines(&lengths, 100.0, 5.0),
+            vec![0..2, 2..3, 3..4, 4..5]
+        );
+        assert_eq!(wrap_lines(&lengths, 1000.0, 5.0), vec![0..5]);
+        assert_eq!(wrap_lines(&[], 100.0, 5.0), vec![]);
+    }
+
+    #[test]
+    fn test_justify() {
+        assert_eq!(Justify::Start.spacing(60.0, 5.0, 3), (0.0, 5.0));
+        assert_eq!(Justify::Center.spacing(60.0, 5.0, 3), (30.0, 5.0));
+        assert_eq!(Justify::End.spacing(60.0, 5.0, 3), (60.0, 5.0));
+        assert_eq!(Justify::SpaceBetween.spacing(60.0, 5.0, 3), (0.0, 35.0));
+        assert_eq!(Justify::SpaceAround.spacing(60.0, 5.0, 3), (10.0, 25.0));
+        assert_eq!(Justify::SpaceEvenly.spacing(60.0, 5.0, 3), (15.0, 20.0));
+        assert_eq!(Justify::End.spacing(-10.0, 5.0, 3), (0.0, 5.0));
+    }
+}
@@ -0,0 +1,321 @@
+//! Rows and columns of cells, shared by the `lazy` and `elm` layout passes. A container uses it
+//! with `Arrangement::Grid`, and its children choose their cell with `Style::cell`.
+
+use crate::draw::to_rect;
+use crate::lazy::Size;
+use crate::widgets::anchor::{Anchor, Horizontal, Vertical};
+use crate::widgets::text::Pixels;
+use crate::{PositionInPixels2d, SizeInPixels2d};
+use macroquad::math::{vec2, Rect};
+use std::collections::HashSet;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Track {
+    Fixed(Pixels),
+    /// as big as the biggest child in it
+    Auto,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grid {
+    pub columns: Vec<Track>,
+    /// children placed beyond these rows get [`Track::Auto`] rows
+    pub rows: Vec<Track>,
+    /// alignment of the children inside their cells, unless the cell overrides it
+    pub horizontal: Horizontal,
+    pub vertical: Vertical,
+}
+
+impl Grid {
+    pub fn new(columns: Vec<Track>, rows: Vec<Track>) -> Self {
+        Self {
+            columns,
+            rows,
+            horizontal: Horizontal::Center,
+            vertical: Vertical::Center,
+        }
+    }
+    /// `columns` auto columns, and as many auto rows as needed
+    pub fn auto(columns: usize) -> Self {
+        Self::new(vec![Track::Auto; columns], Vec::new())
+    }
+    #[must_use]
+    pub fn with_alignment(mut self, horizontal: Horizontal, vertical: Vertical) -> Self {
+        self.horizontal = horizontal;
+        self.vertical = vertical;
+        self
+    }
+}
+
+/// Position of a child in a grid. Children without a cell take the next free cell, row by row.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Cell {
+    pub column: usize,
+    pub row: usize,
+    pub column_span: usize,
+    pub row_span: usize,
+    pub horizontal: Option<Horizontal>,
+    pub vertical: Option<Vertical>,
+}
+
+impl Cell {
+    pub fn new(column: usize, row: usize) -> Self {
+        Self {
+            column,
+            row,
+            column_span: 1,
+            row_span: 1,
+            horizontal: None,
+            vertical: None,
+        }
+    }
+    #[must_use]
+    pub fn span(mut self, columns: usize, rows: usize) -> Self {
+        self.column_span = columns.max(1);
+        self.row_span = rows.max(1);
+        self
+    }
+    #[must_use]
+    pub fn align(mut self, horizontal: Horizontal, vertical: Vertical) -> Self {
+        self.horizontal = Some(horizontal);
+        self.vertical = Some(vertical);
+        self
+    }
+    fn covers(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (self.column..self.column + self.column_span).flat_map(move |column| {
+            (self.row..self.row + self.row_span).map(move |row| (column, row))
+        })
+    }
+}
+
+/// What [`grid_tracks`] needs to know about each child.
+#[derive(Copy, Clone)]
+pub struct GridItem {
+    pub cell: Option<Cell>,
+    pub size: Size,
+    /// size including the margin
+    pub margined: SizeInPixels2d,
+}
+
+/// Sizes of the rows and columns of a grid, and the cell of each child.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GridTracks {
+    pub columns: Vec<Pixels>,
+    pub rows: Vec<Pixels>,
+    pub gap: Pixels,
+    pub cells: Vec<Cell>,
+    horizontal: Horizontal,
+    vertical: Vertical,
+}
+
+impl GridTracks {
+    /// Size of all the cells, without the pad of the container.
+    pub fn size(&self) -> SizeInPixels2d {
+        vec2(
+            span_length(&self.columns, 0, self.columns.len(), self.gap),
+            span_length(&self.rows, 0, self.rows.len(), self.gap),
+        )
+    }
+    /// `origin` is the top left corner of the first cell.
+    pub fn cell_rect(&self, origin: PositionInPixels2d, cell: &Cell) -> Rect {
+        let offset = vec2(
+            span_length(&self.columns, 0, cell.column, self.gap),
+            span_length(&self.rows, 0, cell.row, self.gap),
+        );
+        let gaps = vec2(
+            if cell.column > 0 { self.gap } else { 0.0 },
+            if cell.row > 0 { self.gap } else { 0.0 },
+        );
+        let size = vec2(
+            span_length(&self.columns, cell.column, cell.column_span, self.gap),
+            span_length(&self.rows, cell.row, cell.row_span, self.gap),
+        );
+        to_rect(origin + offset + gaps, size)
+    }
+    /// Where the margined box of a child goes inside its cell.
+    pub fn anchor(&self, origin: PositionInPixels2d, cell: &Cell) -> Anchor {
+        let horizontal = cell.horizontal.unwrap_or(self.horizontal);
+        let vertical = cell.vertical.unwrap_or(self.vertical);
+        Anchor::inside_concrete(
+            self.cell_rect(origin, cell),
+            horizontal,
+            vertical,
+            SizeInPixels2d::ZERO,
+        )
+    }
+}
+
+fn span_length(tracks: &[Pixels], start: usize, span: usize, gap: Pixels) -> Pixels {
+    let end = (start + span).min(tracks.len());
+    if end <= start {
+        return 0.0;
+    }
+    tracks[start..end].iter().sum::<Pixels>() + gap * (end - start - 1) as f32
+}
+
+/// Gives a cell to the children that don't have one, skipping the cells already taken.
+pub fn place_cells(columns: usize, cells: &[Option<Cell>]) -> Vec<Cell> {
+    let columns = columns.max(1);
+    let mut occupied = cells
+        .iter()
+        .flatten()
+        .flat_map(|cell| cell.covers().collect::<Vec<_>>())
+        .collect::<HashSet<_>>();
+    let mut next = 0;
+    cells
+        .iter()
+        .map(|cell| {
+            cell.unwrap_or_else(|| {
+                while occupied.contains(&(next % columns, next / columns)) {
+                    next += 1;
+                }
+                let cell = Cell::new(next % columns, next / columns);
+                occupied.insert((cell.column, cell.row));
+                cell
+            })
+        })
+        .collect()
+}
+
+/// Computes the size of the tracks from the size of the children. Children with
+/// [`Size::Ratio`] depend on the size of their cell, so they don't make auto tracks bigger.
+pub fn grid_tracks(grid: &Grid, items: &[GridItem], gap: Pixels) -> GridTracks {
+    let cells = place_cells(
+        grid.columns.len(),
+        &items.iter().map(|item| item.cell).collect::<Vec<_>>(),
+    );
+    let column_count = cells
+        .iter()
+        .map(|cell| cell.column + cell.column_span)
+        .fold(grid.columns.len(), usize::max);
+    let row_count = cells
+        .iter()
+        .map(|cell| cell.row + cell.row_span)
+        .fold(grid.rows.len(), usize::max);
+    let definition = |tracks: &[Track], index| *tracks.get(index).unwrap_or(&Track::Auto);
+    let column_tracks = (0..column_count)
+        .map(|i| definition(&grid.columns, i))
+        .collect::<Vec<_>>();
+    let row_tracks = (0..row_count)
+        .map(|i| definition(&grid.rows, i))
+        .collect::<Vec<_>>();
+
+    let contributions = items
+        .iter()
+        .map(|item| {
+            if let Size::Ratio { .. } = item.size {
+                SizeInPixels2d::ZERO
+            } else {
+                item.margined
+            }
+        })
+        .collect::<Vec<_>>();
+    let columns = size_tracks(
+        &column_tracks,
+        cells.iter().map(|cell| (cell.column, cell.column_span)),
+        contributions.iter().map(|size| size.x),
+        gap,
+    );
+    let rows = size_tracks(
+        &row_tracks,
+        cells.iter().map(|cell| (cell.row, cell.row_span)),
+        contributions.iter().map(|size| size.y),
+        gap,
+    );
+    GridTracks {
+        columns,
+        rows,
+        gap,
+        cells,
+        horizontal: grid.horizontal,
+        vertical: grid.vertical,
+    }
+}
+
+/// Auto tracks take the size of the biggest child that spans only that track. Then, the children
+/// that span several tracks and don't fit make the auto tracks they span equally bigger.
+fn size_tracks(
+    tracks: &[Track],
+    spans: impl Iterator<Item = (usize, usize)>,
+    lengths: impl Iterator<Item = Pixels>,
+    gap: Pixels,
+) -> Vec<Pixels> {
+    let mut sizes = tracks
+        .iter()
+        .map(|track| match track {
+            Track::Fixed(length) => *length,
+            Track::Auto => 0.0,
+        })
+        .collect::<Vec<_>>();
+    let is_auto = |index: usize| tracks[index] == Track::Auto;
+    let items = spans.zip(lengths).collect::<Vec<_>>();
+    for ((start, span), length) in &items {
+        if *span == 1 && is_auto(*start) {
+            sizes[*start] = sizes[*start].max(*length);
+        }
+    }
+    for ((start, span), length) in &items {
+        if *span > 1 {
+            let missing = length - span_length(&sizes, *start, *span, gap);
+            let autos = (*start..start + span)
+                .filter(|i| is_auto(*i))
+                .collect::<Vec<_>>();
+            if missing > 0.0 && !autos.is_empty() {
+                for i in &autos {
+                    sizes[*i] += missing / autos.len() as f32;
+                }
+            }
+        }
+    }
+    sizes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(cell: Option<Cell>, w: f32, h: f32) -> GridItem {
+        GridItem {
+            cell,
+            size: Size::Fit,
+            margined: vec2(w, h),
+        }
+    }
+
+    #[test]
+    fn test_auto_placement() {
+        let taken = Some(Cell::new(1, 0));
+        let cells = place_cells(2, &[None, taken, None, None]);
+        assert_eq!(
+            cells,
+            vec![
+                Cell::new(0, 0),
+                Cell::new(1, 0),
+                Cell::new(0, 1),
+                Cell::new(1, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tracks_and_spans() {
+        let grid = Grid::new(vec![Track::Fixed(50.0), Track::Auto], vec![]);
+        let items = [
+            item(None, 30.0, 10.0),
+            item(None, 20.0, 15.0),
+            item(Some(Cell::new(0, 1).span(2, 1)), 100.0, 20.0),
+        ];
+        let tracks = grid_tracks(&grid, &items, 5.0);
+        assert_eq!(tracks.columns, vec![50.0, 45.0]);
+        assert_eq!(tracks.rows, vec![15.0, 20.0]);
+        assert_eq!(tracks.size(), vec2(100.0, 40.0));
+        assert_eq!(
+            tracks.cell_rect(vec2(0.0, 0.0), &tracks.cells[1]),
+            Rect::new(55.0, 0.0, 45.0, 15.0)
+        );
+        assert_eq!(
+            tracks.cell_rect(vec2(0.0, 0.0), &tracks.cells[2]),
+            Rect::new(0.0, 20.0, 100.0, 20.0)
+        );
+    }
+}
@@ -0,0 +1,687 @@
+//! The layout passes, shared by the `lazy` ([`crate::lazy::compute_layout`]) and `elm`
+//! ([`crate::elm::widget::compute_layout`]) trees. Each tree implements [`LayoutNode`] for its
+//! widgets and [`LayoutStyle`] for its style, and calls these functions.
+
+use crate::draw::to_rect;
+use crate::fonts::{FontId, DEFAULT_FONT};
+use crate::lazy::flex::{clamp_size, flex_sizes, wrap_lines, FlexItem, Justify};
+use crate::lazy::grid::{grid_tracks, Cell, GridItem};
+use crate::lazy::overflow::{overflow_amounts, overflows, Overflow};
+use crate::lazy::responsive::{choose_variant, Breakpoint, Variant};
+use crate::lazy::{add_contour, resolve_size, Arrangement, Dirty, LayoutState, Pad, Placement};
+use crate::lazy::{Border, Margin, Size, DEFAULT_FONT_SIZE};
+use crate::widgets::anchor::{Anchor, Horizontal, Layout, Spot, Vertical};
+use crate::widgets::text::{AutoFit, Pixels};
+use crate::widgets::Style as Coloring;
+use crate::{PositionInPixels2d, SizeInPixels2d};
+use macroquad::math::{vec2, Rect, Vec2};
+
+/// The part of a style that the layout passes read: a [`Style`], and maybe a border.
+pub trait LayoutStyle: Clone {
+    fn base(&self) -> &Style;
+    /// Drawn at the edge of the widget, outside the pad.
+    fn border(&self) -> Border {
+        Border::new_symmetric(0.0)
+    }
+    /// Space between the edge of the widget and its content: the border and the pad.
+    fn inset(&self) -> Pad {
+        self.border() + self.pad()
+    }
+    fn pad(&self) -> Pad {
+        self.base().pad
+    }
+    fn margin(&self) -> Margin {
+        self.base().margin
+    }
+    fn layout(&self) -> Layout {
+        self.base().layout
+    }
+    fn arrangement(&self) -> &Arrangement {
+        &self.base().arrangement
+    }
+    fn cell(&self) -> Option<Cell> {
+        self.base().cell
+    }
+    fn placement(&self) -> Option<Placement> {
+        self.base().placement
+    }
+    fn gap(&self) -> Pixels {
+        self.base().gap
+    }
+    fn justify(&self) -> Justify {
+        self.base().justify
+    }
+    fn grow(&self) -> f32 {
+        self.base().grow
+    }
+    fn shrink(&self) -> f32 {
+        self.base().shrink
+    }
+    fn size(&self) -> Size {
+        self.base().size
+    }
+    fn min_size(&self) -> SizeInPixels2d {
+        self.base().min_size
+    }
+    fn max_size(&self) -> SizeInPixels2d {
+        self.base().max_size
+    }
+    fn visible(&self) -> bool {
+        self.base().visible
+    }
+}
+
+/// The style of the `lazy` widgets, and the part of the `elm` style that isn't specific to it,
+/// see [`crate::elm::style::Style`].
+///
+/// Not `Copy`, as the tracks of an [`Arrangement::Grid`] and the [`Style::breakpoints`] are
+/// `Vec`s. Reuse a style with `clone()` or pass it by reference, as the widget constructors do.
+#[derive(Clone)]
+pub struct Style {
+    pub pad: Pad,
+    pub margin: Pad,
+    pub layout: Layout,
+    pub arrangement: Arrangement,
+    /// cell of this widget if its parent is arranged in a [`Grid`]
+    pub cell: Option<Cell>,
+    /// where this widget goes if its parent is an [`Arrangement::Stack`]. If None, it's placed
+    /// according to the layout of the parent
+    pub placement: Option<Placement>,
+    /// space between consecutive children along the layout direction
+    pub gap: Pixels,
+    pub justify: Justify,
+    /// weight of this widget when sharing the leftover space with its [`Size::Grow`] siblings
+    pub grow: f32,
+    /// weight of this widget when its siblings don't fit in the parent. 0 means it never shrinks
+    pub shrink: f32,
+    pub font_size: f32,
+    /// if Some and the size is [`Size::Fixed`], texts choose their font size to fit
+    pub auto_fit: Option<AutoFit>,
+    pub font: FontId,
+    pub size: Size,
+    /// the layout never makes this widget smaller than this, e.g. to keep buttons easy to touch
+    pub min_size: SizeInPixels2d,
+    /// the layout never makes this widget bigger than this, unless `min_size` is bigger
+    pub max_size: SizeInPixels2d,
+    pub coloring: Coloring,
+    /// variants of `layout`, `pad`, `font_size` and `visible` for different available sizes,
+    /// applied by the layout passes. See [`crate::lazy::responsive`]
+    pub breakpoints: Vec<Breakpoint>,
+    /// the variant from before any breakpoint was applied, to go back to when none fits. Set by
+    /// [`Self::respond`]
+    pub base_variant: Option<Variant>,
+    /// hidden widgets are not laid out, rendered nor interacted with
+    pub visible: bool,
+    /// texts that don't fit in the space they have are cut and end with "..."
+    pub ellipsis: bool,
+}
+impl Default for Style {
+    fn default() -> Self {
+        Self {
+            pad: Pad::new(DEFAULT_FONT_SIZE * 1.5, DEFAULT_FONT_SIZE),
+            margin: Pad::new_symmetric(0.0),
+            layout: Layout::Vertical {
+                direction: Vertical::Bottom,
+                alignment: Horizontal::Center,
+            },
+            arrangement: Arrangement::Linear,
+            cell: None,
+            placement: None,
+            gap: 0.0,
+            justify: Justify::Start,
+            grow: 1.0,
+            shrink: 0.0,
+            font_size: DEFAULT_FONT_SIZE,
+            auto_fit: None,
+            font: DEFAULT_FONT,
+            size: Size::Fit,
+            min_size: SizeInPixels2d::ZERO,
+            max_size: SizeInPixels2d::splat(f32::INFINITY),
+            coloring: Coloring::default(),
+            breakpoints: Vec::new(),
+            base_variant: None,
+            visible: true,
+            ellipsis: false,
+        }
+    }
+}
+
+impl Style {
+    /// The default style with another layout, which can be a
+    /// [`LayoutV2`](crate::widgets::anchor::LayoutV2) too.
+    pub fn new_layout<L: Into<Layout>>(layout: L) -> Self {
+        Self {
+            layout: layout.into(),
+            ..Default::default()
+        }
+    }
+    /// The part of the style that [`Self::breakpoints`] can change.
+    pub fn variant(&self) -> Variant {
+        Variant {
+            layout: self.layout,
+            pad: self.pad,
+            font_size: self.font_size,
+            visible: self.visible,
+        }
+    }
+    /// Applies the variant of [`Self::breakpoints`] chosen for the `available` space, or the
+    /// variant from before any breakpoint if none fits. Returns whether the style changed.
+    pub fn respond(&mut self, available: SizeInPixels2d) -> bool {
+        if self.breakpoints.is_empty() {
+            return false;
+        }
+        let base = *self.base_variant.get_or_insert(self.variant());
+        let variant = choose_variant(&self.breakpoints, available).unwrap_or(base);
+        if variant == self.variant() {
+            return false;
+        }
+        self.layout = variant.layout;
+        self.pad = variant.pad;
+        self.font_size = variant.font_size;
+        self.visible = variant.visible;
+        true
+    }
+}
+
+impl LayoutStyle for Style {
+    fn base(&self) -> &Style {
+        self
+    }
+}
+
+/// A widget as seen by the layout passes. See the `WidgetTrait` of each tree for what each method
+/// means.
+pub trait LayoutNode {
+    type Style: LayoutStyle;
+    /// The children, borrowed for `'b`.
+    type Child<'b>: LayoutNode<Style = Self::Style> + ?Sized
+    where
+        Self: 'b;
+
+    fn rect(&self) -> Rect {
+        to_rect(self.pos(), self.size())
+    }
+    fn size(&self) -> SizeInPixels2d;
+    fn pos(&self) -> PositionInPixels2d;
+    fn set_size(&mut self, size: SizeInPixels2d);
+    fn set_pos(&mut self, position: PositionInPixels2d);
+    fn content_size(&self) -> Option<SizeInPixels2d>;
+    fn content_offset(&self) -> SizeInPixels2d;
+    fn layout_state(&self) -> LayoutState;
+    fn set_layout_state(&mut self, state: LayoutState);
+    fn respond(&mut self, available: SizeInPixels2d) -> bool;
+    fn limit_width(&mut self, width: Pixels) -> bool;
+    fn clips_children(&self) -> bool;
+    fn kind(&self) -> &'static str;
+    fn text(&self) -> Option<&str>;
+    fn style(&self) -> &Self::Style;
+    fn children(&self) -> Vec<&Self::Child<'_>>;
+    fn children_mut(&mut self) -> Vec<&mut Self::Child<'_>>;
+}
+
+/// Measures the whole tree, fits the root in `rect` according to its [`Size`], distributes the
+/// space among the descendants and finally positions them. Returns the widgets that don't fit.
+pub fn compute_layout<N: LayoutNode + ?Sized>(
+    node: &mut N,
+    rect: Rect,
+    layout: Layout,
+) -> Vec<Overflow> {
+    node.respond(rect.size());
+    refresh_subtree(node);
+    size_subtree(node, rect.size());
+    let style = node.style();
+    let size = resolve_size(style.size(), node.size(), rect.size(), style.margin());
+    node.set_size(clamp_size(size, style.min_size(), style.max_size()));
+    distribute_subtree(node);
+    let anchor = Anchor::inside(rect, layout, vec2(0.0, 0.0));
+    position_subtree(node, anchor);
+    find_overflows(node, rect)
+}
+
+/// Widgets whose margined rect goes beyond the inner rect (inside the pad) of their parent, or
+/// beyond `rect` for the root. See [`crate::lazy::overflow`].
+pub fn find_overflows<N: LayoutNode + ?Sized>(node: &mut N, rect: Rect) -> Vec<Overflow> {
+    let mut found = Vec::new();
+    find_overflows_within(node, rect, &mut Vec::new(), &mut found);
+    found
+}
+
+fn find_overflows_within<N: LayoutNode + ?Sized>(
+    node: &mut N,
+    space: Rect,
+    path: &mut Vec<usize>,
+    found: &mut Vec<Overflow>,
+) {
+    let amounts = overflow_amounts(node.rect(), node.style().margin(), space);
+    found.extend(overflows(path, node.kind(), node.text(), amounts));
+    let mut state = node.layout_state();
+    state.overflow = amounts;
+    node.set_layout_state(state);
+    if node.clips_children() {
+        return;
+    }
+    let inner = add_contour(node.rect(), -node.style().inset());
+    for (i, child) in node.children_mut().into_iter().enumerate() {
+        if child.style().visible() {
+            path.push(i);
+            find_overflows_within(child, inner, path, found);
+            path.pop();
+        }
+    }
+}
+
+/// First pass of the layout: computes the natural size of every widget, bottom-up. Leaves take
+/// their content size, containers fit their children, and [`Size::Fixed`] widgets take their
+/// fixed size. [`crate::lazy::Arrangement::Wrap`] containers break their lines so that they fit
+/// in `available`, which is the space for the margined `node`.
+pub fn set_sizes_within<N: LayoutNode + ?Sized>(node: &mut N, available: SizeInPixels2d) {
+    refresh_subtree(node);
+    size_subtree(node, available)
+}
+
+fn size_subtree<N: LayoutNode + ?Sized>(node: &mut N, available: SizeInPixels2d) {
+    let state = node.layout_state();
+    if let Some((measured_within, natural)) = state.measured {
+        if measured_within == available && pending(node) < Dirty::Size {
+            node.set_size(natural);
+            return;
+        }
+    }
+    let natural = measure(node, available);
+    node.set_size(natural);
+    let mut state = node.layout_state();
+    state.measured = Some((available, natural));
+    node.set_layout_state(state);
+}
+
+fn measure<N: LayoutNode + ?Sized>(node: &mut N, available: SizeInPixels2d) -> SizeInPixels2d {
+    let style = node.style().clone();
+    let margins = style.margin().total();
+    let available = match style.size() {
+        Size::Fixed { w, h } => vec2(w, h),
+        Size::Ratio { w, h } => vec2(w, h) * available - margins,
+        Size::Fit | Size::Grow => available - margins,
+    }
+    .min(style.max_size());
+    if node.content_size().is_some() {
+        node.limit_width(available.x);
+    }
+    if let Some(content_size) = node.content_size() {
+        return natural_size(node.style(), content_size);
+    }
+    let inner = (available - style.inset().total()).max(SizeInPixels2d::ZERO);
+    let mut children = node.children_mut();
+    for child in &mut children {
+        child.respond(inner);
+        if !child.style().visible() {
+            let mut state = child.layout_state();
+            state.dirty = Dirty::Clean;
+            child.set_layout_state(state);
+        }
+    }
+    children.retain(|child| child.style().visible());
+    for child in &mut children {
+        size_subtree(&mut **child, inner);
+    }
+    let subtree = children
+        .iter()
+        .map(|child| pending(&**child))
+        .fold(Dirty::Clean, Dirty::max);
+    let children_size = match style.arrangement() {
+        Arrangement::Linear => linear_size(&style, &children),
+        Arrangement::Grid(grid) => grid_tracks(
+            grid,
+            &grid_items(&children, |child| child.size()),
+            style.gap(),
+        )
+        .size(),
+        Arrangement::Wrap => wrap_size(&style, &children, inner),
+        Arrangement::Stack => margined_sizes(&children)
+            .into_iter()
+            .fold(SizeInPixels2d::ZERO, SizeInPixels2d::max),
+    };
+    let mut state = node.layout_state();
+    state.subtree = subtree;
+    node.set_layout_state(state);
+    natural_size(&style, children_size + style.inset().total())
+}
+
+fn linear_size<C: LayoutNode + ?Sized>(style: &C::Style, children: &[&mut C]) -> SizeInPixels2d {
+    let mut accumulated_size = SizeInPixels2d::new(0.0, 0.0);
+    let parallel = style.layout().parallel_index();
+    let perpendicular = style.layout().perpendicular_index();
+    accumulated_size[parallel] += style.gap() * children.len().saturating_sub(1) as f32;
+    for child in children {
+        let size = child.size();
+        let margin = child.style().margin().total();
+        accumulated_size[parallel] += size[parallel] + margin[parallel];
+        accumulated_size[perpendicular] =
+            accumulated_size[perpendicular].max(size[perpendicular] + margin[perpendicular]);
+    }
+    accumulated_size
+}
+
+fn wrap_size<C: LayoutNode + ?Sized>(
+    style: &C::Style,
+    children: &[&mut C],
+    inner: SizeInPixels2d,
+) -> SizeInPixels2d {
+    let parallel = style.layout().parallel_index();
+    let perpendicular = style.layout().perpendicular_index();
+    let margined = margined_sizes(children);
+    let lengths = margined
+        .iter()
+        .map(|size| size[parallel])
+        .collect::<Vec<_>>();
+    let lines = wrap_lines(&lengths, inner[parallel], style.gap());
+    let mut size = SizeInPixels2d::ZERO;
+    size[perpendicular] = style.gap() * lines.len().saturating_sub(1) as f32;
+    for line in lines {
+        let length =
+            lengths[line.clone()].iter().sum::<f32>() + style.gap() * (line.len() - 1) as f32;
+        size[parallel] = size[parallel].max(length);
+        size[perpendicular] += thickness(&margined[line], perpendicular);
+    }
+    size
+}
+
+fn margined_sizes<C: LayoutNode + ?Sized>(children: &[&mut C]) -> Vec<SizeInPixels2d> {
+    children
+        .iter()
+        .map(|child| child.size() + child.style().margin().total())
+        .collect()
+}
+
+fn thickness(sizes: &[SizeInPixels2d], perpendicular: usize) -> f32 {
+    sizes
+        .iter()
+        .map(|size| size[perpendicular])
+        .fold(0.0, f32::max)
+}
+
+fn grid_items<C: LayoutNode + ?Sized>(
+    children: &[&mut C],
+    size_of: impl Fn(&C) -> SizeInPixels2d,
+) -> Vec<GridItem> {
+    children
+        .iter()
+        .map(|child| {
+            let style = child.style();
+            GridItem {
+                cell: style.cell(),
+                size: style.size(),
+                margined: size_of(&**child) + style.margin().total(),
+            }
+        })
+        .collect()
+}
+
+/// The size given by the measure pass, even if the last distribute pass changed it and then the
+/// measure pass skipped the widget because it didn't change.
+fn measured_size<N: LayoutNode + ?Sized>(node: &N) -> SizeInPixels2d {
+    match node.layout_state().measured {
+        Some((_, natural)) => natural,
+        None => node.size(),
+    }
+}
+
+fn natural_size<S: LayoutStyle>(style: &S, fit: SizeInPixels2d) -> SizeInPixels2d {
+    let size = if let Size::Fixed { w, h } = style.size() {
+        vec2(w, h)
+    } else {
+        fit
+    };
+    clamp_size(size, style.min_size(), style.max_size())
+}
+
+/// Second pass of the layout, once the size of `node` is final: gives the final size to its
+/// children, top-down. See [`flex_sizes`] for how [`Size::Grow`] children share the space, and
+/// [`Size::Ratio`] children take a fraction of the space inside the pad of `node`, or of their
+/// cell in a grid.
+pub fn distribute_sizes<N: LayoutNode + ?Sized>(node: &mut N) {
+    refresh_subtree(node);
+    distribute_subtree(node)
+}
+
+fn distribute_subtree<N: LayoutNode + ?Sized>(node: &mut N) {
+    let style = node.style().clone();
+    let inner = (node.size() - style.inset().total()).max(SizeInPixels2d::ZERO);
+    let children = visible_children_mut(node);
+    let sizes = match style.arrangement() {
+        Arrangement::Linear => {
+            let items = children
+                .iter()
+                .map(|child| {
+                    let child_style = child.style();
+                    FlexItem {
+                        size: child_style.size(),
+                        natural: measured_size(&**child),
+                        margin: child_style.margin(),
+                        grow: child_style.grow(),
+                        shrink: child_style.shrink(),
+                        min_size: child_style.min_size(),
+                        max_size: child_style.max_size(),
+                    }
+                })
+                .collect::<Vec<_>>();
+            flex_sizes(&items, inner, style.layout(), style.gap())
+        }
+        Arrangement::Grid(grid) => {
+            let tracks = grid_tracks(grid, &grid_items(&children, measured_size), style.gap());
+            children
+                .iter()
+                .zip(&tracks.cells)
+                .map(|(child, cell)| {
+                    let cell_size = tracks.cell_rect(Vec2::ZERO, cell).size();
+                    let child_style = child.style();
+                    resolve_size(
+                        child_style.size(),
+                        measured_size(&**child),
+                        cell_size,
+                        child_style.margin(),
+                    )
+                })
+                .collect()
+        }
+        Arrangement::Wrap => children
+            .iter()
+            .map(|child| {
+                let child_style = child.style();
+                let natural = measured_size(&**child);
+                if let Size::Grow = child_style.size() {
+                    natural
+                } else {
+                    resolve_size(child_style.size(), natural, inner, child_style.margin())
+                }
+            })
+            .collect(),
+        Arrangement::Stack => children
+            .iter()
+            .map(|child| {
+                let child_style = child.style();
+                let natural = measured_size(&**child);
+                resolve_size(child_style.size(), natural, inner, child_style.margin())
+            })
+            .collect(),
+    };
+    let mut subtree = Dirty::Clean;
+    for (child, size) in children.into_iter().zip(sizes) {
+        let child_style = child.style();
+        let size = clamp_size(size, child_style.min_size(), child_style.max_size());
+        child.limit_width(size.x);
+        child.set_size(size);
+        let mut state = child.layout_state();
+        let resized = state.distributed != Some(size);
+        if resized || pending(child) == Dirty::Size {
+            state.distributed = Some(size);
+            state.dirty = state.dirty.max(Dirty::Position);
+            child.set_layout_state(state);
+            distribute_subtree(child);
+        }
+        subtree = subtree.max(pending(child));
+    }
+    let mut state = node.layout_state();
+    state.subtree = subtree;
+    node.set_layout_state(state);
+}
+
+/// Last pass of the layout: places the margined `node` at `outer_anchor` and its descendants
+/// inside it. Returns the margined rect.
+pub fn set_positions<N: LayoutNode + ?Sized>(node: &mut N, outer_anchor: Anchor) -> Rect {
+    refresh_subtree(node);
+    position_subtree(node, outer_anchor)
+}
+
+fn position_subtree<N: LayoutNode + ?Sized>(node: &mut N, outer_anchor: Anchor) -> Rect {
+    let margined_size = node.size() + node.style().margin().total();
+    let margined_pos = outer_anchor.get_top_left_pixel(margined_size);
+    let pos = margined_pos + node.style().margin().start();
+    let moved = pos != node.pos();
+    node.set_pos(pos);
+    if !moved && pending(node) == Dirty::Clean {
+        return to_rect(margined_pos, margined_size);
+    }
+
+    let style = node.style().clone();
+    let rect = node.rect().offset(-node.content_offset());
+    let children = visible_children_mut(node);
+    match style.arrangement() {
+        Arrangement::Linear => {
+            set_linear_positions(&style, add_contour(rect, -style.inset()), children)
+        }
+        Arrangement::Grid(grid) => {
+            let tracks = grid_tracks(
+                grid,
+                &grid_items(&children, |child| child.size()),
+                style.gap(),
+            );
+            let origin = rect.point() + style.inset().start();
+            for (child, cell) in children.into_iter().zip(&tracks.cells) {
+                position_subtree(child, tracks.anchor(origin, cell));
+            }
+        }
+        Arrangement::Wrap => set_wrap_positions(&style, rect, children),
+        Arrangement::Stack => {
+            let inner = add_contour(rect, -style.inset());
+            for child in children {
+                let anchor = match child.style().placement() {
+                    Some(placement) => placement.anchor(inner),
+                    None => Anchor::inside(inner, style.layout(), SizeInPixels2d::ZERO),
+                };
+                position_subtree(child, anchor);
+            }
+        }
+    }
+    let mut state = node.layout_state();
+    state.dirty = Dirty::Clean;
+    state.subtree = Dirty::Clean;
+    node.set_layout_state(state);
+    to_rect(margined_pos, margined_size)
+}
+
+fn visible_children_mut<N: LayoutNode + ?Sized>(node: &mut N) -> Vec<&mut N::Child<'_>> {
+    node.children_mut()
+        .into_iter()
+        .filter(|child| child.style().visible())
+        .collect()
+}
+
+/// The most that has to be computed again in the layout of `node` or its descendants, according to
+/// the [`LayoutState::subtree`] cached by [`refresh_subtree`].
+fn pending<N: LayoutNode + ?Sized>(node: &N) -> Dirty {
+    let state = node.layout_state();
+    state.dirty.max(state.subtree)
+}
+
+/// Caches in [`LayoutState::subtree`] how dirty the descendants of every widget are, in one walk
+/// of the tree. Hidden children only count for their own change, e.g. being hidden, but their
+/// subtree is refreshed too for when they are shown again. Returns [`pending`] for `node`.
+fn refresh_subtree<N: LayoutNode + ?Sized>(node: &mut N) -> Dirty {
+    let mut subtree = Dirty::Clean;
+    for child in node.children_mut() {
+        let child_dirty = refresh_subtree(child);
+        subtree = subtree.max(if child.style().visible() {
+            child_dirty
+        } else {
+            child.layout_state().dirty
+        });
+    }
+    let mut state = node.layout_state();
+    state.subtree = subtree;
+    node.set_layout_state(state);
+    state.dirty.max(subtree)
+}
+
+/// The most that has to be computed again in the layout of `node` or any of its descendants.
+pub fn subtree_dirty<N: LayoutNode + ?Sized>(node: &N) -> Dirty {
+    let mut dirty = node.layout_state().dirty;
+    for child in node.children() {
+        if dirty == Dirty::Size {
+            break;
+        }
+        dirty = dirty.max(subtree_dirty(child));
+    }
+    dirty
+}
+
+/// Each line is placed like an [`Arrangement::Linear`] container as big as the line, and the
+/// lines are aligned according to the alignment of the layout.
+fn set_wrap_positions<C: LayoutNode + ?Sized>(style: &C::Style, rect: Rect, children: Vec<&mut C>) {
+    let layout = style.layout();
+    let parallel = layout.parallel_index();
+    let perpendicular = layout.perpendicular_index();
+    let inner = (rect.size() - style.inset().total()).max(SizeInPixels2d::ZERO);
+    let margined = margined_sizes(&children);
+    let lengths = margined
+        .iter()
+        .map(|size| size[parallel])
+        .collect::<Vec<_>>();
+    let lines = wrap_lines(&lengths, inner[parallel], style.gap());
+    let thicknesses = lines
+        .iter()
+        .map(|line| thickness(&margined[line.clone()], perpendicular))
+        .collect::<Vec<_>>();
+    let total =
+        thicknesses.iter().sum::<f32>() + style.gap() * lines.len().saturating_sub(1) as f32;
+    let free = (inner[perpendicular] - total).max(0.0);
+    let mut offset = match layout.get_alignment() {
+        Spot::Beginning => 0.0,
+        Spot::Center => free * 0.5,
+        Spot::End => free,
+    };
+    let mut children = children.into_iter();
+    for (line, thickness) in lines.into_iter().zip(thicknesses) {
+        let mut position = rect.point() + style.inset().start();
+        position[perpendicular] += offset;
+        let mut size = inner;
+        size[perpendicular] = thickness;
+        let line_children = children.by_ref().take(line.len()).collect();
+        set_linear_positions(style, to_rect(position, size), line_children);
+        offset += thickness + style.gap();
+    }
+}
+
+/// Places `children` one after another inside `inner_rect`, which doesn't include the pad.
+fn set_linear_positions<C: LayoutNode + ?Sized>(
+    style: &C::Style,
+    inner_rect: Rect,
+    children: Vec<&mut C>,
+) {
+    // the first child has to Anchor::inside, and the next ones need to Anchor::next_to, so create an empty rect as first child
+    let layout = style.layout();
+    let initial_anchor = Anchor::inside(inner_rect, layout, SizeInPixels2d::ZERO);
+    let zero2d = SizeInPixels2d::default();
+    let mut previous_rect = initial_anchor.get_rect(zero2d);
+    let parallel = layout.parallel_index();
+    let inner = inner_rect.size();
+    let mut free = inner[parallel] - style.gap() * children.len().saturating_sub(1) as f32;
+    for child in &children {
+        free -= child.size()[parallel] + child.style().margin().total()[parallel];
+    }
+    let (mut spacing, between) = style.justify().spacing(free, style.gap(), children.len());
+    for child in children {
+        let anchor = Anchor::next_to(previous_rect, layout, spacing);
+        previous_rect = position_subtree(child, anchor);
+        spacing = between;
+    }
+}
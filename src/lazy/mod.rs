@@ -1,15 +1,27 @@
-use crate::draw::{draw_rect_lines, to_rect};
+use crate::draw::{draw_rect, draw_rect_lines, to_rect};
+use crate::lazy::dump::{describe_kind, describe_text, DescribeFn};
+use crate::lazy::flex::clamp_size;
+use crate::lazy::grid::Grid;
+use crate::lazy::overflow::Overflow;
 use crate::widgets::anchor::{Anchor, Horizontal, Layout, Vertical};
 use crate::widgets::text::Pixels;
-use crate::widgets::{Interaction, Style as Coloring};
+use crate::widgets::Interaction;
 use crate::{PositionInPixels2d, SizeInPixels2d};
-use macroquad::color::{Color, BLACK, BLUE, ORANGE};
-use macroquad::prelude::{vec2, Font, Rect, Vec2};
+use macroquad::color::{Color, BLACK, BLUE, ORANGE, RED};
+use macroquad::prelude::{vec2, Rect, Vec2};
 use std::any::Any;
-use std::ops::{Index, IndexMut};
+use std::ops::{Add, Index, IndexMut, Neg};
 
 pub mod button;
+pub mod checkbox;
+pub mod dump;
+pub mod flex;
+pub mod grid;
+pub mod layout;
+pub mod overflow;
 pub mod panel;
+pub mod responsive;
+pub mod scroll;
 pub mod slider;
 pub mod text;
 
@@ -39,7 +51,53 @@ pub trait WidgetTrait {
     }
     fn set_pos(&mut self, position: PositionInPixels2d);
     fn set_size(&mut self, size: SizeInPixels2d);
+    /// The size that leaves need for their own content (e.g. a text), pad included. Containers
+    /// return None, and their size is computed from their children in [`set_sizes`].
+    fn content_size(&self) -> Option<SizeInPixels2d> {
+        None
+    }
+    /// How much the children are moved left and up from where the layout would put them, e.g.
+    /// by scrolling.
+    fn content_offset(&self) -> SizeInPixels2d {
+        SizeInPixels2d::ZERO
+    }
+    /// Widgets that don't keep a [`LayoutState`] are laid out again every time.
+    fn layout_state(&self) -> LayoutState {
+        LayoutState::default()
+    }
+    fn set_layout_state(&mut self, _state: LayoutState) {}
+    /// Switches to the variant of the style for the `available` space (see [`Style::respond`]).
+    /// Returns whether the style changed.
+    fn respond(&mut self, _available: SizeInPixels2d) -> bool {
+        false
+    }
+    /// Limits the width (pad included) of the content of leaves that allow it, like texts with
+    /// [`Style::ellipsis`], and measures them again if their style changed. Returns whether the
+    /// content size changed.
+    fn limit_width(&mut self, _width: Pixels) -> bool {
+        false
+    }
+    /// Whether the children can go beyond this widget on purpose, like in a scroll container, so
+    /// that they are not reported as overflows.
+    fn clips_children(&self) -> bool {
+        false
+    }
+    /// Makes the next layout compute again at least `dirty` for this widget. Call it after
+    /// changing the style or the children of a widget.
+    fn mark_dirty(&mut self, dirty: Dirty) {
+        let mut state = self.layout_state();
+        state.dirty = state.dirty.max(dirty);
+        self.set_layout_state(state);
+    }
 
+    /// Name of the kind of widget, for [`dump::dump`].
+    fn kind(&self) -> &'static str {
+        "Widget"
+    }
+    /// The text shown by this widget itself (not by its children), for [`dump::dump`].
+    fn text(&self) -> Option<&str> {
+        None
+    }
     fn style(&self) -> &Style;
     // fn children_mut(&mut self) -> &mut Widgets;
     // fn children(&self) -> &Widgets;
@@ -68,6 +126,13 @@ impl<T: Renderable + WidgetTrait + Interactable> RenderableWidget for T {}
 pub struct WidgetData<Custom> {
     pos: PositionInPixels2d,
     size: Option<SizeInPixels2d>,
+    content_size: Option<SizeInPixels2d>,
+    layout_state: LayoutState,
+    /// computes `content_size` again after the style changed, for leaves whose content depends
+    /// on it, like texts
+    measure: Option<fn(&mut Self)>,
+    /// names the widget in dumps, see [`DescribeFn`]
+    describe: Option<DescribeFn<Custom>>,
     style: Style,
     pub custom: Custom,
     pub children: Widgets,
@@ -78,6 +143,10 @@ impl<Custom> WidgetData<Custom> {
         Self {
             pos: Default::default(),
             size: Default::default(),
+            content_size: None,
+            layout_state: LayoutState::default(),
+            measure: None,
+            describe: None,
             style,
             custom,
             children,
@@ -87,17 +156,32 @@ impl<Custom> WidgetData<Custom> {
         Self {
             pos: Default::default(),
             size: Default::default(),
+            content_size: None,
+            layout_state: LayoutState::default(),
+            measure: None,
+            describe: None,
             style,
             custom,
             children: Vec::new(),
         }
     }
 }
+impl<Custom> WidgetData<Custom> {
+    /// Marks the widget to be measured again in the next layout.
+    pub fn style_mut(&mut self) -> &mut Style {
+        self.layout_state.dirty = Dirty::Size;
+        &mut self.style
+    }
+}
 impl<Custom: Default> WidgetData<Custom> {
     pub fn container(style: Style, children: Widgets) -> Self {
         Self {
             pos: Default::default(),
             size: Default::default(),
+            content_size: None,
+            layout_state: LayoutState::default(),
+            measure: None,
+            describe: None,
             style,
             custom: Default::default(),
             children,
@@ -107,6 +191,10 @@ impl<Custom: Default> WidgetData<Custom> {
         Self {
             pos: Default::default(),
             size: Default::default(),
+            content_size: None,
+            layout_state: LayoutState::default(),
+            measure: None,
+            describe: None,
             style,
             custom: Default::default(),
             children: Vec::new(),
@@ -118,6 +206,10 @@ impl<Custom: Default> Default for WidgetData<Custom> {
         Self {
             pos: Default::default(),
             size: Default::default(),
+            content_size: None,
+            layout_state: LayoutState::default(),
+            measure: None,
+            describe: None,
             style: Default::default(),
             custom: Default::default(),
             children: Default::default(),
@@ -139,9 +231,44 @@ impl<Custom> WidgetTrait for WidgetData<Custom> {
         self.pos = position;
     }
     fn set_size(&mut self, size: SizeInPixels2d) {
-        if self.size.is_none() {
-            self.size = Some(size);
+        self.size = Some(size);
+    }
+    fn content_size(&self) -> Option<SizeInPixels2d> {
+        self.content_size
+    }
+    fn layout_state(&self) -> LayoutState {
+        self.layout_state
+    }
+    fn set_layout_state(&mut self, state: LayoutState) {
+        self.layout_state = state;
+    }
+    fn respond(&mut self, available: SizeInPixels2d) -> bool {
+        let changed = self.style.respond(available);
+        if changed {
+            self.layout_state.dirty = Dirty::Size;
+            if let Some(measure) = self.measure {
+                measure(self);
+            }
+        }
+        changed
+    }
+    fn limit_width(&mut self, width: Pixels) -> bool {
+        let limit = self.style.ellipsis.then_some(width);
+        if self.layout_state.width_limit == limit && self.layout_state.dirty < Dirty::Size {
+            return false;
         }
+        self.layout_state.width_limit = limit;
+        let previous_size = self.content_size;
+        if let Some(measure) = self.measure {
+            measure(self);
+        }
+        previous_size != self.content_size
+    }
+    fn kind(&self) -> &'static str {
+        describe_kind(&self.custom, self.describe)
+    }
+    fn text(&self) -> Option<&str> {
+        describe_text(&self.custom, self.describe)
     }
     fn style(&self) -> &Style {
         &self.style
@@ -177,101 +304,227 @@ impl<Custom: Default> From<Style> for WidgetData<Custom> {
     }
 }
 
-#[derive(Copy, Clone)]
+/// How much of the layout of a widget has to be computed again. See [`LayoutState`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Dirty {
+    Clean,
+    /// the widget or its descendants have to be positioned again
+    Position,
+    /// the content or the style of the widget changed, so it has to be measured again
+    #[default]
+    Size,
+}
+
+/// What the layout passes remember about a widget, so that they can skip the subtrees that didn't
+/// change since the last layout, e.g. when only the screen size changed.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct LayoutState {
+    pub dirty: Dirty,
+    /// the most dirty of the descendants, cached at the start of each pass so that the passes
+    /// don't walk the subtree of every widget
+    pub subtree: Dirty,
+    /// available space in the last measure pass, and the natural size it gave
+    pub measured: Option<(SizeInPixels2d, SizeInPixels2d)>,
+    /// final size given by the last distribute pass
+    pub distributed: Option<SizeInPixels2d>,
+    /// width (pad included) that texts with [`Style::ellipsis`] have to fit in
+    pub width_limit: Option<Pixels>,
+    /// how much the margined widget goes beyond the space of its parent in the last layout
+    pub overflow: SizeInPixels2d,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum Size {
     /// minimum possible
     Fit,
-    /// maximum possible
+    /// maximum possible, shared with the siblings that also grow
     Grow,
     Fixed {
         w: Pixels,
         h: Pixels,
     },
+    /// fraction of the space inside the pad of the parent, margin included
     Ratio {
         w: f32,
         h: f32,
     },
 }
 
+/// Size of a widget whose margined box has up to `available` space, given its `natural` size
+/// computed by [`set_sizes`]. A growing widget never gets smaller than its natural size.
+pub fn resolve_size(
+    size: Size,
+    natural: SizeInPixels2d,
+    available: SizeInPixels2d,
+    margin: Margin,
+) -> SizeInPixels2d {
+    let margins = margin.total();
+    match size {
+        Size::Fit | Size::Fixed { .. } => natural,
+        Size::Grow => natural.max(available - margins),
+        Size::Ratio { w, h } => (vec2(w, h) * available - margins).max(SizeInPixels2d::ZERO),
+    }
+}
+
+/// How a container places its children.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub enum Arrangement {
+    /// one after another in the direction of the [`Layout`]
+    #[default]
+    Linear,
+    /// in the cells of a [`Grid`]
+    Grid(Grid),
+    /// like `Linear`, but starting a new line (or column, for vertical layouts) when the next
+    /// child doesn't fit. [`Size::Grow`] children don't grow
+    Wrap,
+    /// all the children on top of each other, in the same rect, placed by their
+    /// [`Style::placement`]. Later children are drawn over earlier ones
+    Stack,
+}
+
+/// Where a child of an [`Arrangement::Stack`] container goes inside it.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Placement {
+    pub horizontal: Horizontal,
+    pub vertical: Vertical,
+    pub offset: SizeInPixels2d,
+}
+impl Placement {
+    pub fn new(horizontal: Horizontal, vertical: Vertical) -> Self {
+        Self {
+            horizontal,
+            vertical,
+            offset: SizeInPixels2d::ZERO,
+        }
+    }
+    /// e.g. to make a badge stick out of the corner of an icon
+    #[must_use]
+    pub fn with_offset(mut self, offset: SizeInPixels2d) -> Self {
+        self.offset = offset;
+        self
+    }
+    /// Anchor for the margined box of the child, inside the pad of the container.
+    pub fn anchor(&self, inner: Rect) -> Anchor {
+        let mut anchor = Anchor::inside_concrete(inner, self.horizontal, self.vertical, Vec2::ZERO);
+        anchor.offset_v(self.offset);
+        anchor
+    }
+}
+
 pub type Margin = Pad;
+/// Thickness of each side of a border, drawn at the edge of a widget, outside the pad.
+pub type Border = Pad;
 
-#[derive(Copy, Clone)]
+/// Space at each side of a widget. `new` and `new_symmetric` give the same space to opposite
+/// sides, and `sides` allows a different value for each one.
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub struct Pad {
-    pub x: f32,
-    pub y: f32,
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+    pub left: f32,
 }
 impl Pad {
+    /// `x` for left and right, `y` for top and bottom.
     pub fn new(x: f32, y: f32) -> Self {
-        Self { x, y }
+        Self::sides(y, x, y, x)
     }
     pub fn new_symmetric(pad: f32) -> Self {
         Self::new(pad, pad)
     }
+    /// Clockwise from the top, like in CSS.
+    pub fn sides(top: f32, right: f32, bottom: f32, left: f32) -> Self {
+        Self {
+            top,
+            right,
+            bottom,
+            left,
+        }
+    }
     pub fn position(&self, position: PositionInPixels2d) -> PositionInPixels2d {
-        position + vec2(self.x, self.y)
+        position + self.start()
+    }
+    /// The left and top sides.
+    pub fn start(&self) -> SizeInPixels2d {
+        vec2(self.left, self.top)
+    }
+    /// The right and bottom sides.
+    pub fn end(&self) -> SizeInPixels2d {
+        vec2(self.right, self.bottom)
     }
+    #[deprecated(note = "sides can differ now, use `start`, `end` or `total`")]
     pub fn vec2(&self) -> SizeInPixels2d {
-        vec2(self.x, self.y)
+        self.start()
     }
-}
-impl From<Vec2> for Pad {
-    fn from(value: Vec2) -> Self {
-        Pad::new(value.x, value.y)
+    /// Space taken in each axis: left + right, top + bottom.
+    pub fn total(&self) -> SizeInPixels2d {
+        self.start() + self.end()
+    }
+    /// Sets both sides of `axis` (0 for left and right, 1 for top and bottom).
+    pub fn set_axis(&mut self, axis: usize, pad: f32) {
+        if axis == 0 {
+            self.left = pad;
+            self.right = pad;
+        } else if axis == 1 {
+            self.top = pad;
+            self.bottom = pad;
+        } else {
+            panic!("Pad axes must be 0 or 1 but was {}", axis)
+        }
     }
 }
+/// The start side of `axis` (0 for left, 1 for top), like [`Pad::start`]. Prefer the fields, as
+/// the end side might be different.
 impl Index<usize> for Pad {
     type Output = f32;
-    fn index(&self, index: usize) -> &Self::Output {
-        if index == 0 {
-            &self.x
-        } else if index == 1 {
-            &self.y
+    fn index(&self, axis: usize) -> &Self::Output {
+        if axis == 0 {
+            &self.left
+        } else if axis == 1 {
+            &self.top
         } else {
-            panic!("Pad indexes must be 0 or 1 but was {}", index)
+            panic!("Pad indexes must be 0 or 1 but was {}", axis)
         }
     }
 }
+/// Only changes the start side of `axis`, use [`Pad::set_axis`] to change both sides.
 impl IndexMut<usize> for Pad {
-    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        if index == 0 {
-            &mut self.x
-        } else if index == 1 {
-            &mut self.y
+    fn index_mut(&mut self, axis: usize) -> &mut Self::Output {
+        if axis == 0 {
+            &mut self.left
+        } else if axis == 1 {
+            &mut self.top
         } else {
-            panic!("Pad indexes must be 0 or 1 but was {}", index)
+            panic!("Pad indexes must be 0 or 1 but was {}", axis)
         }
     }
 }
-
-pub type FontId = usize;
-
-#[derive(Clone)]
-pub struct Style {
-    pub pad: Pad,
-    pub margin: Pad,
-    pub layout: Layout,
-    pub font_size: f32,
-    pub font: Option<Font>,
-    pub size: Size,
-    pub coloring: Coloring,
+impl From<Vec2> for Pad {
+    fn from(value: Vec2) -> Self {
+        Pad::new(value.x, value.y)
+    }
 }
-impl Default for Style {
-    fn default() -> Self {
-        Self {
-            pad: Pad::new(DEFAULT_FONT_SIZE * 1.5, DEFAULT_FONT_SIZE),
-            margin: Pad::new_symmetric(0.0),
-            layout: Layout::Vertical {
-                direction: Vertical::Bottom,
-                alignment: Horizontal::Center,
-            },
-            font_size: DEFAULT_FONT_SIZE,
-            font: None,
-            size: Size::Fit,
-            coloring: Coloring::default(),
-        }
+impl Neg for Pad {
+    type Output = Pad;
+    fn neg(self) -> Self::Output {
+        Pad::sides(-self.top, -self.right, -self.bottom, -self.left)
+    }
+}
+impl Add for Pad {
+    type Output = Pad;
+    fn add(self, other: Pad) -> Self::Output {
+        Pad::sides(
+            self.top + other.top,
+            self.right + other.right,
+            self.bottom + other.bottom,
+            self.left + other.left,
+        )
     }
 }
 
+pub use crate::fonts::FontId;
+pub use layout::Style;
+
 pub struct Ui {
     pub style: Style,
     pub screen_size: SizeInPixels2d,
@@ -307,15 +560,13 @@ pub struct Container<W: WidgetTrait> {
 }
 impl<W: WidgetTrait> Container<W> {
     pub fn close(mut self) -> W {
+        self.widget.respond(self.max_size);
+        set_sizes_within(&mut self.widget, self.max_size);
         let style = self.widget.style();
-        match style.size {
-            Size::Grow => {
-                self.widget.set_size(self.max_size);
-            }
-            Size::Fit | Size::Fixed { .. } | Size::Ratio { .. } => {
-                unimplemented!()
-            }
-        }
+        let size = resolve_size(style.size, self.widget.size(), self.max_size, style.margin);
+        let size = clamp_size(size, style.min_size, style.max_size);
+        self.widget.set_size(size);
+        distribute_sizes(&mut self.widget);
         self.widget
     }
 }
@@ -348,60 +599,147 @@ pub fn container<'a, 'b>(
     }
 }
 
-pub fn set_sizes(node: &mut dyn RenderableWidget) {
-    let mut accumulated_size = SizeInPixels2d::new(0.0, 0.0);
-    let style = node.style();
-    let parallel = style.layout.parallel_index();
-    let perpendicular = style.layout.perpendicular_index();
-    for child in node.children_mut() {
-        set_sizes(child);
-        let size = child.size();
-        let margin = child.style().margin.vec2();
-        accumulated_size[parallel] += size[parallel] + 2.0 * margin[parallel];
-        accumulated_size[perpendicular] =
-            accumulated_size[perpendicular].max(size[perpendicular] + 2.0 * margin[perpendicular]);
-    }
-    accumulated_size += 2.0 * node.style().pad.vec2();
-    node.set_size(accumulated_size);
-    // println!(
-    //     "size: {}, margin: {}, pad: {}",
-    //     node.node().size(),
-    //     node.node().style().margin.vec2(),
-    //     node.node().style().pad.vec2()
-    // );
-}
-pub fn set_positions(node: &mut dyn RenderableWidget, outer_anchor: Anchor) -> Rect {
-    let margined_size = node.size() + node.style().margin.vec2() * 2.0;
-    let margined_pos = outer_anchor.get_top_left_pixel(margined_size);
-    let pos = margined_pos + node.style().margin.vec2();
-    node.set_pos(pos);
-
-    // the first child has to Anchor::inside, and the next ones need to Anchor::next_to, so create an empty rect as first child
-    let initial_anchor = Anchor::inside(node.rect(), node.style().layout, node.style().pad.vec2());
-    let zero2d = SizeInPixels2d::default();
-    let mut previous_rect = initial_anchor.get_rect(zero2d);
-    let style = node.style().clone();
-    for child in node.children_mut() {
-        let anchor = Anchor::next_to(previous_rect, style.layout, 0.0);
-        previous_rect = set_positions(child, anchor);
-    }
-    to_rect(margined_pos, margined_size)
-}
-
-pub fn add_contour(rect: Rect, size: SizeInPixels2d) -> Rect {
-    let mut new_position = rect.point() - size;
-    let mut new_size = rect.size() + size * 2.0;
-    let center = rect.center();
-    for i in 0..1 {
+/// Measures the whole tree, fits the root in `rect` according to its [`Size`], distributes the
+/// space among the descendants and finally positions them. Returns the widgets that don't fit.
+pub fn compute_layout<L: Into<Layout>>(
+    node: &mut dyn WidgetTrait,
+    rect: Rect,
+    layout: L,
+) -> Vec<Overflow> {
+    layout::compute_layout(node, rect, layout.into())
+}
+
+/// Widgets whose margined rect goes beyond the inner rect (inside the pad) of their parent, or
+/// beyond `rect` for the root. See [`overflow`].
+pub fn find_overflows(node: &mut dyn WidgetTrait, rect: Rect) -> Vec<Overflow> {
+    layout::find_overflows(node, rect)
+}
+
+/// First pass of the layout: computes the natural size of every widget, bottom-up. Leaves take
+/// their [`WidgetTrait::content_size`], containers fit their children, and [`Size::Fixed`] widgets
+/// take their fixed size.
+pub fn set_sizes(node: &mut dyn WidgetTrait) {
+    set_sizes_within(node, SizeInPixels2d::splat(f32::INFINITY))
+}
+
+/// Like [`set_sizes`], but [`Arrangement::Wrap`] containers break their lines so that they fit in
+/// `available`, which is the space for the margined `node`.
+pub fn set_sizes_within(node: &mut dyn WidgetTrait, available: SizeInPixels2d) {
+    layout::set_sizes_within(node, available)
+}
+
+/// Second pass of the layout, see [`layout::distribute_sizes`].
+pub fn distribute_sizes(node: &mut dyn WidgetTrait) {
+    layout::distribute_sizes(node)
+}
+
+/// Last pass of the layout, see [`layout::set_positions`].
+pub fn set_positions(node: &mut dyn WidgetTrait, outer_anchor: Anchor) -> Rect {
+    layout::set_positions(node, outer_anchor)
+}
+
+/// The most that has to be computed again in the layout of `node` or any of its descendants.
+pub fn subtree_dirty(node: &dyn WidgetTrait) -> Dirty {
+    layout::subtree_dirty(node)
+}
+
+impl<'a> layout::LayoutNode for dyn WidgetTrait + 'a {
+    type Style = Style;
+    type Child<'b>
+        = dyn WidgetTrait + 'b
+    where
+        Self: 'b;
+
+    fn size(&self) -> SizeInPixels2d {
+        WidgetTrait::size(self)
+    }
+    fn pos(&self) -> PositionInPixels2d {
+        WidgetTrait::pos(self)
+    }
+    fn set_size(&mut self, size: SizeInPixels2d) {
+        WidgetTrait::set_size(self, size)
+    }
+    fn set_pos(&mut self, position: PositionInPixels2d) {
+        WidgetTrait::set_pos(self, position)
+    }
+    fn content_size(&self) -> Option<SizeInPixels2d> {
+        WidgetTrait::content_size(self)
+    }
+    fn content_offset(&self) -> SizeInPixels2d {
+        WidgetTrait::content_offset(self)
+    }
+    fn layout_state(&self) -> LayoutState {
+        WidgetTrait::layout_state(self)
+    }
+    fn set_layout_state(&mut self, state: LayoutState) {
+        WidgetTrait::set_layout_state(self, state)
+    }
+    fn respond(&mut self, available: SizeInPixels2d) -> bool {
+        WidgetTrait::respond(self, available)
+    }
+    fn limit_width(&mut self, width: Pixels) -> bool {
+        WidgetTrait::limit_width(self, width)
+    }
+    fn clips_children(&self) -> bool {
+        WidgetTrait::clips_children(self)
+    }
+    fn kind(&self) -> &'static str {
+        WidgetTrait::kind(self)
+    }
+    fn text(&self) -> Option<&str> {
+        WidgetTrait::text(self)
+    }
+    fn style(&self) -> &Style {
+        WidgetTrait::style(self)
+    }
+    fn children(&self) -> Vec<&Self::Child<'_>> {
+        WidgetTrait::children(self)
+            .into_iter()
+            .map(|child| child as &Self::Child<'_>)
+            .collect()
+    }
+    fn children_mut(&mut self) -> Vec<&mut Self::Child<'_>> {
+        WidgetTrait::children_mut(self)
+            .into_iter()
+            .map(|child| child as &mut Self::Child<'_>)
+            .collect()
+    }
+}
+
+/// Grows each side of `rect` by the matching side of `contour`, or shrinks it if negative.
+pub fn add_contour<P: Into<Pad>>(rect: Rect, contour: P) -> Rect {
+    let contour = contour.into();
+    let mut new_position = rect.point() - contour.start();
+    let mut new_size = rect.size() + contour.total();
+    for i in 0..2 {
         if new_size[i] < 0.0 {
             // size reduced so much that the rect flips. collapse rather than invert
-            new_position[i] = center[i];
+            new_position[i] += new_size[i] * 0.5;
             new_size[i] = 0.0;
         }
     }
     to_rect(new_position, new_size)
 }
 
+/// Draws each side of `border` inside `rect`, as thick as the matching side.
+pub fn draw_border(rect: Rect, border: Border, color: Color) {
+    let Border {
+        top,
+        right,
+        bottom,
+        left,
+    } = border;
+    let middle_h = (rect.h - top - bottom).max(0.0);
+    draw_rect(Rect::new(rect.x, rect.y, rect.w, top), color);
+    draw_rect(
+        Rect::new(rect.x, rect.bottom() - bottom, rect.w, bottom),
+        color,
+    );
+    draw_rect(Rect::new(rect.x, rect.y + top, left, middle_h), color);
+    let right_x = rect.right() - right;
+    draw_rect(Rect::new(right_x, rect.y + top, right, middle_h), color);
+}
+
 pub const DEBUGGING_ALPHA: f32 = 0.5;
 pub const DEBUGGING_THICKNESS: f32 = 8.0;
 
@@ -412,9 +750,12 @@ pub fn with_alpha(color: Color, alpha: f32) -> Color {
 fn draw_debug_widget<C>(widget_data: &WidgetData<C>) {
     let half_thickness = DEBUGGING_THICKNESS * 0.5;
     let contours = [
-        (Vec2::new(0.0, 0.0), BLACK),
-        (widget_data.style.margin.vec2(), BLUE),
-        (-widget_data.style.pad.vec2() + half_thickness, ORANGE),
+        (Pad::new_symmetric(0.0), BLACK),
+        (widget_data.style.margin, BLUE),
+        (
+            -widget_data.style.pad + Pad::new_symmetric(half_thickness),
+            ORANGE,
+        ),
     ];
     let rect = widget_data.rect();
     for (contour, color) in contours {
@@ -422,31 +763,22 @@ fn draw_debug_widget<C>(widget_data: &WidgetData<C>) {
         let rect_color = with_alpha(color, DEBUGGING_ALPHA);
         draw_rect_lines(drawn_rect, DEBUGGING_THICKNESS, rect_color);
     }
+    if widget_data.layout_state.overflow != SizeInPixels2d::ZERO {
+        draw_rect_lines(rect, DEBUGGING_THICKNESS, RED);
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::lazy::button::Button;
+    use crate::lazy::flex::Justify;
+    use crate::lazy::grid::Cell;
     use crate::lazy::panel::Panel;
+    use crate::lazy::responsive::{Breakpoint, Variant};
     use crate::lazy::text::{render_text, Text};
-    use macroquad::prelude::TextDimensions;
-
-    fn mock_measure(
-        text: &str,
-        _font: Option<&Font>,
-        font_size: u16,
-        font_scale: f32,
-    ) -> TextDimensions {
-        let font_size = font_size as f32 * font_scale;
-        let height_to_width_coef = 0.75;
-        let height_to_offset_coef = 0.75;
-        TextDimensions {
-            width: height_to_width_coef * font_size * text.len() as f32,
-            height: font_size,
-            offset_y: height_to_offset_coef * font_size,
-        }
-    }
+    use crate::testing::mock_measure;
+
     #[test]
     fn test_basic_layout() {
         let style = &Style::default();
@@ -504,4 +836,571 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_grow_fixed_and_ratio() {
+        let style = Style {
+            layout: Layout::horizontal(Horizontal::Right, Vertical::Top),
+            ..Default::default()
+        };
+        let with_size = |size| Style {
+            size,
+            ..style.clone()
+        };
+        let fixed = with_size(Size::Fixed { w: 100.0, h: 50.0 });
+        let mut panel = Panel::<()>::container(
+            with_size(Size::Grow),
+            vec![
+                Box::new(Text::new_generic(
+                    &fixed,
+                    "fixed",
+                    mock_measure,
+                    render_text,
+                )),
+                Box::new(Panel::<()>::leaf(with_size(Size::Grow))),
+                Box::new(Panel::<()>::leaf(with_size(Size::Ratio {
+                    w: 0.25,
+                    h: 0.5,
+                }))),
+            ],
+        );
+        let screen = Rect::new(0.0, 0.0, 1000.0, 500.0);
+        compute_layout(&mut panel, screen, style.layout);
+        assert_eq!(panel.rect(), screen);
+        let children = panel.children();
+        assert_eq!(children[0].rect(), Rect::new(24.0, 16.0, 100.0, 50.0));
+        assert_eq!(children[1].rect(), Rect::new(124.0, 16.0, 614.0, 468.0));
+        assert_eq!(children[2].rect(), Rect::new(738.0, 16.0, 238.0, 234.0));
+    }
+
+    #[test]
+    fn test_justify_space_between() {
+        let style = Style {
+            layout: Layout::horizontal(Horizontal::Right, Vertical::Top),
+            pad: Pad::new_symmetric(0.0),
+            size: Size::Fixed { w: 300.0, h: 20.0 },
+            gap: 10.0,
+            justify: Justify::SpaceBetween,
+            ..Default::default()
+        };
+        let child = || -> Box<dyn RenderableWidget> {
+            Box::new(Panel::<()>::leaf(Style {
+                size: Size::Fixed { w: 50.0, h: 20.0 },
+                ..style.clone()
+            }))
+        };
+        let mut panel = Panel::<()>::container(style.clone(), vec![child(), child(), child()]);
+        compute_layout(&mut panel, Rect::new(0.0, 0.0, 1000.0, 500.0), style.layout);
+        let xs = panel
+            .children()
+            .iter()
+            .map(|child| child.pos().x)
+            .collect::<Vec<_>>();
+        assert_eq!(xs, vec![0.0, 125.0, 250.0]);
+    }
+
+    #[test]
+    fn test_grid_layout() {
+        let style = Style {
+            pad: Pad::new_symmetric(10.0),
+            gap: 5.0,
+            ..Default::default()
+        };
+        let leaf = |w, h, cell: Option<Cell>| -> Box<dyn RenderableWidget> {
+            Box::new(Panel::<()>::leaf(Style {
+                size: Size::Fixed { w, h },
+                cell,
+                ..style.clone()
+            }))
+        };
+        let grid = Grid::auto(2).with_alignment(Horizontal::Left, Vertical::Top);
+        let corner = Cell::new(1, 1).align(Horizontal::Right, Vertical::Bottom);
+        let mut panel = Panel::<()>::grid(
+            style.clone(),
+            grid,
+            vec![
+                leaf(40.0, 20.0, None),
+                leaf(10.0, 10.0, Some(corner)),
+                leaf(20.0, 30.0, None),
+                leaf(30.0, 10.0, None),
+            ],
+        );
+        let top_left = Layout::vertical(Vertical::Bottom, Horizontal::Left);
+        compute_layout(&mut panel, Rect::new(0.0, 0.0, 1000.0, 500.0), top_left);
+        assert_eq!(panel.size(), vec2(85.0, 65.0));
+        let rects = panel
+            .children()
+            .iter()
+            .map(|child| child.rect())
+            .collect::<Vec<_>>();
+        assert_eq!(rects[0], Rect::new(10.0, 10.0, 40.0, 20.0));
+        assert_eq!(rects[1], Rect::new(65.0, 45.0, 10.0, 10.0));
+        assert_eq!(rects[2], Rect::new(55.0, 10.0, 20.0, 30.0));
+        assert_eq!(rects[3], Rect::new(10.0, 45.0, 30.0, 10.0));
+    }
+
+    #[test]
+    fn test_wrap_layout() {
+        let style = Style {
+            layout: Layout::horizontal(Horizontal::Right, Vertical::Top),
+            arrangement: Arrangement::Wrap,
+            pad: Pad::new_symmetric(0.0),
+            gap: 10.0,
+            ..Default::default()
+        };
+        let leaf = |w| -> Box<dyn RenderableWidget> {
+            Box::new(Panel::<()>::leaf(Style {
+                size: Size::Fixed { w, h: 20.0 },
+                ..style.clone()
+            }))
+        };
+        let mut panel =
+            Panel::<()>::container(style.clone(), vec![leaf(40.0), leaf(40.0), leaf(30.0)]);
+        compute_layout(&mut panel, Rect::new(0.0, 0.0, 100.0, 500.0), style.layout);
+        assert_eq!(panel.size(), vec2(90.0, 50.0));
+        let positions = panel
+            .children()
+            .iter()
+            .map(|child| child.pos())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            positions,
+            vec![vec2(0.0, 0.0), vec2(50.0, 0.0), vec2(0.0, 30.0)]
+        );
+    }
+
+    #[test]
+    fn test_stack_layout() {
+        let style = Style {
+            pad: Pad::new_symmetric(0.0),
+            ..Default::default()
+        };
+        let leaf = |w, h, placement| -> Box<dyn RenderableWidget> {
+            Box::new(Panel::<()>::leaf(Style {
+                size: Size::Fixed { w, h },
+                placement,
+                ..style.clone()
+            }))
+        };
+        let badge = Placement::new(Horizontal::Right, Vertical::Top).with_offset(vec2(5.0, -5.0));
+        let mut panel = Panel::<()>::stack(
+            style.clone(),
+            vec![
+                leaf(40.0, 40.0, None),
+                leaf(10.0, 10.0, Some(badge)),
+                leaf(20.0, 10.0, None),
+            ],
+        );
+        let top_left = Layout::vertical(Vertical::Bottom, Horizontal::Left);
+        compute_layout(&mut panel, Rect::new(0.0, 0.0, 1000.0, 500.0), top_left);
+        assert_eq!(panel.rect(), Rect::new(0.0, 0.0, 40.0, 40.0));
+        let rects = panel
+            .children()
+            .iter()
+            .map(|child| child.rect())
+            .collect::<Vec<_>>();
+        assert_eq!(rects[0], Rect::new(0.0, 0.0, 40.0, 40.0));
+        assert_eq!(rects[1], Rect::new(35.0, -5.0, 10.0, 10.0));
+        assert_eq!(rects[2], Rect::new(10.0, 0.0, 20.0, 10.0));
+    }
+
+    #[test]
+    fn test_min_and_max_size() {
+        let style = Style {
+            min_size: vec2(200.0, 60.0),
+            max_size: vec2(300.0, 100.0),
+            ..Default::default()
+        };
+        let mut short = Text::new_generic(&style, "ok", mock_measure, render_text);
+        set_sizes(&mut short);
+        assert_eq!(short.size(), vec2(200.0, 60.0));
+
+        let long_text = "a text that is too long for a readable button";
+        let mut long = Text::new_generic(&style, long_text, mock_measure, render_text);
+        set_sizes(&mut long);
+        assert_eq!(long.size(), vec2(300.0, 60.0));
+    }
+
+    #[test]
+    fn test_dirty_layout() {
+        let style = Style {
+            pad: Pad::new_symmetric(0.0),
+            size: Size::Grow,
+            layout: Layout::vertical(Vertical::Bottom, Horizontal::Left),
+            ..Default::default()
+        };
+        let leaf = |size| -> Box<dyn RenderableWidget> {
+            Box::new(Panel::<()>::leaf(Style {
+                size,
+                ..style.clone()
+            }))
+        };
+        let tree = || {
+            let nested = Panel::<()>::container(style.clone(), vec![leaf(Size::Grow)]);
+            Panel::<()>::container(
+                style.clone(),
+                vec![leaf(Size::Fixed { w: 40.0, h: 20.0 }), Box::new(nested)],
+            )
+        };
+        fn rects(node: &dyn WidgetTrait) -> Vec<Rect> {
+            let mut rects = vec![node.rect()];
+            for child in node.children() {
+                rects.extend(rects_of(child));
+            }
+            rects
+        }
+        fn rects_of(node: &dyn RenderableWidget) -> Vec<Rect> {
+            rects(node)
+        }
+        let small = Rect::new(0.0, 0.0, 200.0, 100.0);
+        let big = Rect::new(0.0, 0.0, 300.0, 150.0);
+
+        let mut panel = tree();
+        compute_layout(&mut panel, small, style.layout);
+        assert_eq!(subtree_dirty(&panel), Dirty::Clean);
+
+        panel.children_mut()[0].set_pos(vec2(999.0, 999.0));
+        compute_layout(&mut panel, small, style.layout);
+        assert_eq!(
+            panel.children()[0].pos(),
+            vec2(999.0, 999.0),
+            "nothing changed, so nothing was positioned again"
+        );
+        panel.children_mut()[0].mark_dirty(Dirty::Position);
+        compute_layout(&mut panel, small, style.layout);
+        assert_eq!(panel.children()[0].pos(), vec2(0.0, 0.0));
+
+        compute_layout(&mut panel, big, style.layout);
+        let mut fresh = tree();
+        compute_layout(&mut fresh, big, style.layout);
+        assert_eq!(rects(&panel), rects(&fresh));
+        assert_eq!(panel.children()[1].children()[0].size(), vec2(300.0, 130.0));
+
+        panel.style_mut().gap = 10.0;
+        compute_layout(&mut panel, big, style.layout);
+        let mut fresh = tree();
+        fresh.style_mut().gap = 10.0;
+        compute_layout(&mut fresh, big, style.layout);
+        assert_eq!(rects(&panel), rects(&fresh));
+        assert_eq!(panel.children()[1].rect().y, 30.0);
+
+        let mut children = panel.children_mut();
+        let mut grandchildren = children[1].children_mut();
+        grandchildren[0].set_size(vec2(1.0, 1.0));
+        grandchildren[0].mark_dirty(Dirty::Size);
+        drop(grandchildren);
+        drop(children);
+        compute_layout(&mut panel, big, style.layout);
+        assert_eq!(panel.children()[1].children()[0].size(), vec2(300.0, 120.0));
+        assert_eq!(subtree_dirty(&panel), Dirty::Clean);
+    }
+
+    #[test]
+    fn test_breakpoints() {
+        let style = Style {
+            pad: Pad::new_symmetric(0.0),
+            ..Default::default()
+        };
+        let base = style.variant();
+        let responsive = |narrow: Variant, wide: Variant| Style {
+            breakpoints: vec![
+                Breakpoint::new(SizeInPixels2d::ZERO, narrow),
+                Breakpoint::min_width(800.0, wide),
+            ],
+            ..style.clone()
+        };
+        let vertical = Variant {
+            layout: Layout::vertical(Vertical::Bottom, Horizontal::Left),
+            ..base
+        };
+        let horizontal = Variant {
+            layout: Layout::horizontal(Horizontal::Right, Vertical::Top),
+            ..base
+        };
+        let big_font = Variant {
+            font_size: 32.0,
+            ..base
+        };
+        let hidden = Variant {
+            visible: false,
+            ..base
+        };
+        let fixed = |w, h, style: Style| -> Box<dyn RenderableWidget> {
+            Box::new(Panel::<()>::leaf(Style {
+                size: Size::Fixed { w, h },
+                ..style
+            }))
+        };
+        let text_style = responsive(big_font, base);
+        let mut panel = Panel::<()>::container(
+            responsive(vertical, horizontal),
+            vec![
+                Box::new(Text::new_generic(
+                    &text_style,
+                    "ab",
+                    mock_measure,
+                    render_text,
+                )),
+                fixed(20.0, 20.0, style.clone()),
+                fixed(10.0, 10.0, responsive(hidden, base)),
+            ],
+        );
+        let top_left = Layout::vertical(Vertical::Bottom, Horizontal::Left);
+        let rects = |panel: &Panel<()>| {
+            panel
+                .children()
+                .iter()
+                .filter(|child| child.style().visible)
+                .map(|child| child.rect())
+                .collect::<Vec<_>>()
+        };
+
+        compute_layout(&mut panel, Rect::new(0.0, 0.0, 1000.0, 500.0), top_left);
+        assert_eq!(panel.size(), vec2(54.0, 20.0));
+        assert_eq!(
+            rects(&panel),
+            vec![
+                Rect::new(0.0, 0.0, 24.0, 16.0),
+                Rect::new(24.0, 0.0, 20.0, 20.0),
+                Rect::new(44.0, 0.0, 10.0, 10.0),
+            ]
+        );
+
+        compute_layout(&mut panel, Rect::new(0.0, 0.0, 400.0, 800.0), top_left);
+        assert_eq!(panel.size(), vec2(48.0, 52.0));
+        assert_eq!(
+            rects(&panel),
+            vec![
+                Rect::new(0.0, 0.0, 48.0, 32.0),
+                Rect::new(0.0, 32.0, 20.0, 20.0)
+            ]
+        );
+
+        compute_layout(&mut panel, Rect::new(0.0, 0.0, 1000.0, 500.0), top_left);
+        assert_eq!(panel.size(), vec2(54.0, 20.0));
+    }
+
+    #[test]
+    fn test_breakpoints_go_back_to_the_base_style() {
+        let vertical = Style {
+            pad: Pad::new_symmetric(0.0),
+            layout: Layout::vertical(Vertical::Bottom, Horizontal::Left),
+            ..Default::default()
+        };
+        let horizontal = Variant {
+            layout: Layout::horizontal(Horizontal::Right, Vertical::Top),
+            ..vertical.variant()
+        };
+        let fixed = |w, h| -> Box<dyn RenderableWidget> {
+            Box::new(Panel::<()>::leaf(Style {
+                size: Size::Fixed { w, h },
+                ..Default::default()
+            }))
+        };
+        // no breakpoint for narrow screens, so they get the style itself
+        let style = Style {
+            breakpoints: vec![Breakpoint::min_width(800.0, horizontal)],
+            ..vertical.clone()
+        };
+        let mut panel = Panel::<()>::container(style, vec![fixed(20.0, 20.0), fixed(10.0, 10.0)]);
+        let top_left = vertical.layout;
+
+        compute_layout(&mut panel, Rect::new(0.0, 0.0, 1000.0, 500.0), top_left);
+        assert_eq!(panel.size(), vec2(30.0, 20.0));
+        compute_layout(&mut panel, Rect::new(0.0, 0.0, 400.0, 800.0), top_left);
+        assert_eq!(panel.size(), vec2(20.0, 30.0));
+        assert!(panel.style().variant() == vertical.variant());
+        compute_layout(&mut panel, Rect::new(0.0, 0.0, 1000.0, 500.0), top_left);
+        assert_eq!(panel.size(), vec2(30.0, 20.0));
+    }
+
+    #[test]
+    fn test_layout_v2() {
+        use crate::widgets::anchor::{Direction, LayoutV2, Sense, Spot};
+        fn rects(node: &dyn WidgetTrait) -> Vec<Rect> {
+            let mut rects = vec![node.rect()];
+            for child in node.children() {
+                rects.extend(rects_of(child));
+            }
+            rects
+        }
+        fn rects_of(node: &dyn RenderableWidget) -> Vec<Rect> {
+            rects(node)
+        }
+        // the panel goes inside the 600x400 rect, and the texts "first" (108x48 with the pad) and
+        // "second" (120x48) follow each other inside its pad of 24x16
+        fn expected(v2: LayoutV2) -> Vec<Rect> {
+            let spot = |spot, [beginning, center, end]: [f32; 3]| match spot {
+                Spot::Beginning => beginning,
+                Spot::Center => center,
+                Spot::End => end,
+            };
+            let sense = |sense, [forwards, backwards, centered]: [[f32; 3]; 3]| match sense {
+                Sense::Forwards => forwards,
+                Sense::Backwards => backwards,
+                Sense::Centered => centered,
+            };
+            match v2.direction {
+                Direction::Horizontal => {
+                    let [x, first, second] = sense(
+                        v2.sense,
+                        [
+                            [0.0, 24.0, 132.0],
+                            [324.0, 144.0, 24.0],
+                            [162.0, 138.0, 246.0],
+                        ],
+                    );
+                    let y = spot(v2.alignment, [0.0, 160.0, 320.0]);
+                    vec![
+                        Rect::new(x, y, 276.0, 80.0),
+                        Rect::new(x + first, y + 16.0, 108.0, 48.0),
+                        Rect::new(x + second, y + 16.0, 120.0, 48.0),
+                    ]
+                }
+                Direction::Vertical => {
+                    let [y, first, second] = sense(
+                        v2.sense,
+                        [[0.0, 16.0, 64.0], [272.0, 64.0, 16.0], [136.0, 64.0, 112.0]],
+                    );
+                    let x = spot(v2.alignment, [0.0, 216.0, 432.0]);
+                    let first_x = spot(v2.alignment, [24.0, 30.0, 36.0]);
+                    vec![
+                        Rect::new(x, y, 168.0, 128.0),
+                        Rect::new(x + first_x, y + first, 108.0, 48.0),
+                        Rect::new(x + 24.0, y + second, 120.0, 48.0),
+                    ]
+                }
+            }
+        }
+        let rect = Rect::new(0.0, 0.0, 600.0, 400.0);
+        for direction in [Direction::Horizontal, Direction::Vertical] {
+            for sense in [Sense::Forwards, Sense::Backwards, Sense::Centered] {
+                for alignment in [Spot::Beginning, Spot::Center, Spot::End] {
+                    let v2 = LayoutV2::new(direction, sense, alignment);
+                    let style = Style::new_layout(v2);
+                    let tree = || {
+                        let text = |s| -> Box<dyn RenderableWidget> {
+                            Box::new(Text::new_generic(&style, s, mock_measure, render_text))
+                        };
+                        Panel::<()>::container(style.clone(), vec![text("first"), text("second")])
+                    };
+                    let mut with_v2 = tree();
+                    compute_layout(&mut with_v2, rect, v2);
+                    let mut with_v1 = tree();
+                    compute_layout(&mut with_v1, rect, Layout::from(v2));
+                    assert_eq!(rects(&with_v2), rects(&with_v1), "{:?}", v2);
+                    assert_eq!(rects(&with_v2), expected(v2), "{:?}", v2);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_add_contour() {
+        let rect = Rect::new(10.0, 20.0, 100.0, 50.0);
+        let sides = Pad::sides(1.0, 2.0, 3.0, 4.0);
+        assert_eq!(add_contour(rect, sides), Rect::new(6.0, 19.0, 106.0, 54.0));
+        assert_eq!(add_contour(rect, -sides), Rect::new(14.0, 21.0, 94.0, 46.0));
+        let collapsed = add_contour(rect, -Pad::sides(30.0, 0.0, 40.0, 0.0));
+        assert_eq!(collapsed, Rect::new(10.0, 40.0, 100.0, 0.0));
+    }
+
+    #[test]
+    fn test_sided_pad_and_margin() {
+        let style = Style {
+            pad: Pad::sides(1.0, 2.0, 3.0, 4.0),
+            layout: Layout::vertical(Vertical::Bottom, Horizontal::Left),
+            ..Default::default()
+        };
+        let leaf = Panel::<()>::leaf(Style {
+            size: Size::Fixed { w: 40.0, h: 20.0 },
+            margin: Pad::sides(10.0, 0.0, 5.0, 20.0),
+            ..style.clone()
+        });
+        let layout = style.layout;
+        let mut panel = Panel::<()>::container(style, vec![Box::new(leaf)]);
+        compute_layout(&mut panel, Rect::new(0.0, 0.0, 200.0, 100.0), layout);
+        assert_eq!(panel.rect(), Rect::new(0.0, 0.0, 66.0, 39.0));
+        assert_eq!(
+            panel.children()[0].rect(),
+            Rect::new(24.0, 11.0, 40.0, 20.0)
+        );
+    }
+
+    #[test]
+    fn test_ellipsis_and_overflows() {
+        let style = Style {
+            pad: Pad::new_symmetric(0.0),
+            font_size: 20.0,
+            ..Style::default()
+        };
+        let cut = Style {
+            ellipsis: true,
+            ..style.clone()
+        };
+        let text = |style, s| Box::new(Text::new_generic(style, s, mock_measure, render_text));
+        let menu = Style {
+            size: Size::Fixed { w: 100.0, h: 100.0 },
+            ..style.clone()
+        };
+        let mut panel = Panel::<()>::container(
+            menu,
+            vec![text(&cut, "translation"), text(&style, "translation")],
+        );
+        let overflows =
+            compute_layout(&mut panel, Rect::new(0.0, 0.0, 1000.0, 500.0), style.layout);
+        assert_eq!(panel.children()[0].text(), Some("tra..."));
+        assert_eq!(panel.children()[0].size(), vec2(90.0, 20.0));
+        assert_eq!(panel.children()[0].layout_state().overflow, Vec2::ZERO);
+        assert_eq!(
+            overflows,
+            vec![Overflow {
+                path: vec![1],
+                kind: "Text",
+                text: Some("translation".to_string()),
+                axis: 0,
+                amount: 65.0,
+            }]
+        );
+        assert_eq!(panel.children()[1].layout_state().overflow, vec2(65.0, 0.0));
+
+        // with more space the whole text is shown again
+        panel.style.size = Size::Fixed { w: 200.0, h: 100.0 };
+        panel.mark_dirty(Dirty::Size);
+        let overflows =
+            compute_layout(&mut panel, Rect::new(0.0, 0.0, 1000.0, 500.0), style.layout);
+        assert_eq!(panel.children()[0].text(), Some("translation"));
+        assert_eq!(overflows, Vec::new());
+    }
+
+    #[test]
+    fn test_dump_basic_layout() {
+        let style = &Style::default();
+        let text = |s| Box::new(Text::new_generic(style, s, mock_measure, render_text));
+        let hidden = Style {
+            visible: false,
+            margin: Pad::sides(1.0, 2.0, 3.0, 4.0),
+            ..style.clone()
+        };
+        let mut panel = Panel::<Interaction>::container(
+            style.clone(),
+            vec![
+                text("some text"),
+                Box::new(Button::container(style.clone(), vec![text("some button")])),
+                Box::new(Panel::<()>::leaf(hidden)),
+            ],
+        );
+        compute_layout(
+            &mut panel,
+            Rect::new(0.0, 0.0, 2000.0, 1000.0),
+            style.layout,
+        );
+        dump::assert_golden(
+            &dump::dump(&panel),
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/tests/golden/lazy_basic_layout.txt"
+            ),
+        );
+    }
 }
@@ -0,0 +1,106 @@
+//! Widgets that don't fit where the layout put them, e.g. a translated text that is longer than
+//! the menu it's in. [`crate::lazy::compute_layout`] and [`crate::elm::widget::compute_layout`]
+//! return them, and with [`crate::lazy::DEBUG_WIDGETS`] the lazy widgets are highlighted.
+//!
+//! Texts with [`crate::lazy::Style::ellipsis`] are cut instead, so they only overflow if not even
+//! the ellipsis fits.
+
+use crate::lazy::{add_contour, Pad};
+use crate::widgets::text::Pixels;
+use crate::SizeInPixels2d;
+use macroquad::math::Rect;
+use std::fmt::{Display, Formatter};
+
+/// Overflows smaller than this are rounding errors.
+const TOLERANCE: Pixels = 0.5;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Overflow {
+    /// indexes of the children to follow from the root to reach the widget
+    pub path: Vec<usize>,
+    pub kind: &'static str,
+    pub text: Option<String>,
+    /// 0 for horizontal, 1 for vertical
+    pub axis: usize,
+    /// pixels of the margined widget that are outside the space it has
+    pub amount: Pixels,
+}
+
+impl Display for Overflow {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.kind)?;
+        if let Some(text) = &self.text {
+            write!(f, " {:?}", text)?;
+        }
+        let axis = if self.axis == 0 {
+            "horizontally"
+        } else {
+            "vertically"
+        };
+        write!(
+            f,
+            " at {:?} overflows {} pixels {}",
+            self.path, self.amount, axis
+        )
+    }
+}
+
+/// How much of `rect` plus `margin` is outside `space`, in each axis.
+pub fn overflow_amounts(rect: Rect, margin: Pad, space: Rect) -> SizeInPixels2d {
+    let margined = add_contour(rect, margin);
+    let before = (space.point() - margined.point()).max(SizeInPixels2d::ZERO);
+    let after = (margined.point() + margined.size() - space.point() - space.size())
+        .max(SizeInPixels2d::ZERO);
+    let amounts = before + after;
+    SizeInPixels2d::select(
+        amounts.cmpgt(SizeInPixels2d::splat(TOLERANCE)),
+        amounts,
+        SizeInPixels2d::ZERO,
+    )
+}
+
+/// One [`Overflow`] per axis where `amounts` is not zero.
+pub fn overflows(
+    path: &[usize],
+    kind: &'static str,
+    text: Option<&str>,
+    amounts: SizeInPixels2d,
+) -> Vec<Overflow> {
+    (0..2)
+        .filter(|axis| amounts[*axis] > 0.0)
+        .map(|axis| Overflow {
+            path: path.to_vec(),
+            kind,
+            text: text.map(str::to_string),
+            axis,
+            amount: amounts[axis],
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use macroquad::math::vec2;
+
+    #[test]
+    fn test_overflow_amounts() {
+        let space = Rect::new(0.0, 0.0, 100.0, 50.0);
+        let inside = Rect::new(10.0, 10.0, 20.0, 20.0);
+        assert_eq!(
+            overflow_amounts(inside, Pad::new_symmetric(5.0), space),
+            SizeInPixels2d::ZERO
+        );
+        let wide = Rect::new(-10.0, 10.0, 130.0, 20.0);
+        assert_eq!(
+            overflow_amounts(wide, Pad::new(0.0, 20.0), space),
+            vec2(30.0, 10.0)
+        );
+        let overflows = overflows(&[1, 0], "Text", Some("long"), vec2(30.0, 0.0));
+        assert_eq!(overflows.len(), 1);
+        assert_eq!(
+            overflows[0].to_string(),
+            "Text \"long\" at [1, 0] overflows 30 pixels horizontally"
+        );
+    }
+}
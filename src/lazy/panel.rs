@@ -1,6 +1,9 @@
 use crate::draw::draw_rect;
+use crate::lazy::dump::Describe;
+use crate::lazy::grid::Grid;
 use crate::lazy::{
-    draw_debug_widget, Interactable, Renderable, WidgetData, WidgetTrait, DEBUG_WIDGETS,
+    draw_debug_widget, Arrangement, Interactable, Renderable, Style, WidgetData, WidgetTrait,
+    Widgets, DEBUG_WIDGETS,
 };
 use crate::widgets::Interaction;
 use std::any::Any;
@@ -11,6 +14,11 @@ pub type Panel<Response> = WidgetData<PanelBase<Response>>;
 pub struct PanelBase<Response> {
     phantom_data: PhantomData<Response>,
 }
+impl<Response> Describe for PanelBase<Response> {
+    fn kind(&self) -> &'static str {
+        "Panel"
+    }
+}
 impl<Response> Default for PanelBase<Response> {
     fn default() -> Self {
         Self {
@@ -19,6 +27,19 @@ impl<Response> Default for PanelBase<Response> {
     }
 }
 
+impl<Response> Panel<Response> {
+    /// A panel that places its children in the cells of `grid`. See [`Style::cell`].
+    pub fn grid(mut style: Style, grid: Grid, children: Widgets) -> Self {
+        style.arrangement = Arrangement::Grid(grid);
+        Self::container(style, children)
+    }
+    /// A panel whose children are on top of each other. See [`Style::placement`].
+    pub fn stack(mut style: Style, children: Widgets) -> Self {
+        style.arrangement = Arrangement::Stack;
+        Self::container(style, children)
+    }
+}
+
 impl<Response: 'static> Panel<Response> {
     pub fn interact_tt<OneOffResponse: 'static>(
         &mut self,
@@ -57,7 +78,9 @@ impl<Response> Renderable for Panel<Response> {
             draw_debug_widget(widget);
         }
         for child in self.children() {
-            child.render();
+            if child.style().visible {
+                child.render();
+            }
         }
     }
 }
@@ -66,7 +89,9 @@ impl<Response> Interactable for Panel<Response> {
     fn interact(&mut self) -> Vec<Box<dyn Any>> {
         let mut interactions = Vec::new();
         for child in self.children_mut() {
-            interactions.extend(child.interact());
+            if child.style().visible {
+                interactions.extend(child.interact());
+            }
         }
         interactions
     }
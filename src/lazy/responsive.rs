@@ -0,0 +1,77 @@
+//! Variants of a style for different available sizes, e.g. a horizontal layout on wide screens
+//! and a vertical one on portrait phones. Shared by the `lazy` and `elm` styles, and chosen by the
+//! layout passes every time they run, so a resize is enough to switch variant.
+
+use crate::lazy::Pad;
+use crate::widgets::anchor::Layout;
+use crate::SizeInPixels2d;
+
+/// The part of a style that can change with the available size.
+#[derive(Copy, Clone, PartialEq)]
+pub struct Variant {
+    pub layout: Layout,
+    pub pad: Pad,
+    pub font_size: f32,
+    /// hidden widgets are not laid out, rendered nor interacted with
+    pub visible: bool,
+}
+
+#[derive(Copy, Clone, PartialEq)]
+pub struct Breakpoint {
+    /// the variant is used if the space available for the margined widget is at least this big
+    pub min_size: SizeInPixels2d,
+    pub variant: Variant,
+}
+
+impl Breakpoint {
+    pub fn new(min_size: SizeInPixels2d, variant: Variant) -> Self {
+        Self { min_size, variant }
+    }
+    /// Used if the available width is at least `width`.
+    pub fn min_width(width: f32, variant: Variant) -> Self {
+        Self::new(SizeInPixels2d::new(width, 0.0), variant)
+    }
+    /// Used if the available height is at least `height`.
+    pub fn min_height(height: f32, variant: Variant) -> Self {
+        Self::new(SizeInPixels2d::new(0.0, height), variant)
+    }
+}
+
+/// The variant of the last breakpoint that fits in `available`, so breakpoints should go from
+/// the smallest to the biggest. None if there are no breakpoints or none fits.
+pub fn choose_variant(breakpoints: &[Breakpoint], available: SizeInPixels2d) -> Option<Variant> {
+    breakpoints
+        .iter()
+        .rev()
+        .find(|breakpoint| available.cmpge(breakpoint.min_size).all())
+        .map(|breakpoint| breakpoint.variant)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::anchor::{Horizontal, Vertical};
+    use macroquad::math::vec2;
+
+    #[test]
+    fn test_choose_variant() {
+        let narrow = Variant {
+            layout: Layout::vertical(Vertical::Bottom, Horizontal::Center),
+            pad: Pad::new_symmetric(5.0),
+            font_size: 24.0,
+            visible: true,
+        };
+        let wide = Variant {
+            layout: Layout::horizontal(Horizontal::Right, Vertical::Center),
+            font_size: 16.0,
+            ..narrow
+        };
+        let breakpoints = [
+            Breakpoint::new(SizeInPixels2d::ZERO, narrow),
+            Breakpoint::min_width(800.0, wide),
+        ];
+        assert!(choose_variant(&breakpoints, vec2(400.0, 900.0)) == Some(narrow));
+        assert!(choose_variant(&breakpoints, vec2(800.0, 600.0)) == Some(wide));
+        assert!(choose_variant(&breakpoints[1..], vec2(400.0, 900.0)).is_none());
+    }
+}
@@ -0,0 +1,446 @@
+//! Containers that show only a part of their children, which can be scrolled with the mouse
+//! wheel (with shift for horizontal scrolling), by dragging the scrollbars, or by clicking the
+//! scrollbar tracks to move one page.
+//!
+//! The children are positioned where they are seen, i.e. already moved by the scroll offset (see
+//! [`WidgetTrait::content_offset`]), so they hit-test with the usual input. The parts that are
+//! outside the viewport are clipped with [`crate::clip`], both when rendering and interacting.
+
+use crate::clip;
+use crate::draw::{draw_rect, to_rect};
+use crate::input::input_macroquad::InputMacroquad;
+use crate::input::input_trait::InputTrait;
+use crate::lazy::{
+    add_contour, draw_debug_widget, set_positions, Dirty, Interactable, LayoutState, Pad,
+    Renderable, Style, WidgetData, WidgetTrait, Widgets, WidgetsView, WidgetsViewMut,
+    DEBUG_WIDGETS,
+};
+use crate::widgets::anchor::Anchor;
+use crate::widgets::text::Pixels;
+use crate::widgets::{Interaction, Style as Coloring};
+use crate::{PositionInPixels2d, SizeInPixels2d};
+use macroquad::input::{KeyCode, MouseButton};
+use macroquad::math::{vec2, Rect};
+use std::any::Any;
+
+pub const SCROLLBAR_THICKNESS: Pixels = 12.0;
+/// pixels scrolled per wheel step
+pub const WHEEL_STEP: Pixels = 40.0;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Drag {
+    axis: usize,
+    /// distance from the start of the thumb to the mouse when the drag started
+    grab: Pixels,
+}
+
+/// Scroll position of a scroll container, kept across frames. Shared by [`Scroll`] and
+/// [`crate::elm::scroll::Scroll`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ScrollState {
+    /// how much the content is moved left and up. Always between zero and [`max_offset`]
+    pub offset: SizeInPixels2d,
+    drag: Option<Drag>,
+}
+
+/// The track is the whole length of the scrollbar, and the thumb is the part that represents the
+/// visible part of the content.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Scrollbar {
+    /// 0 for the horizontal scrollbar, 1 for the vertical one
+    pub axis: usize,
+    pub track: Rect,
+    pub thumb: Rect,
+}
+
+impl ScrollState {
+    pub fn is_dragging(&self) -> bool {
+        self.drag.is_some()
+    }
+
+    /// Updates the offset from the input. `extent` is the size of the content, from
+    /// [`content_extent`]. Returns whether the offset changed, in which case the children have
+    /// to be positioned again.
+    pub fn update(
+        &mut self,
+        viewport: Rect,
+        extent: SizeInPixels2d,
+        input: &dyn InputTrait,
+    ) -> bool {
+        let previous = self.offset;
+        let max = max_offset(viewport.size(), extent);
+        let bars = scrollbars(viewport, extent, self.offset);
+        let mouse = input.mouse_position();
+        let hovered = clip::clip_rect(viewport).contains(mouse);
+        if hovered {
+            let mut wheel = input.mouse_wheel().clamp(vec2(-1.0, -1.0), vec2(1.0, 1.0));
+            let shift =
+                input.is_key_down(KeyCode::LeftShift) || input.is_key_down(KeyCode::RightShift);
+            if shift || max.y == 0.0 {
+                wheel = vec2(wheel.x + wheel.y, 0.0);
+            }
+            self.offset -= wheel * WHEEL_STEP;
+        }
+        if hovered && input.is_mouse_button_pressed(MouseButton::Left) {
+            for bar in bars.iter().flatten() {
+                let axis = bar.axis;
+                if bar.thumb.contains(mouse) {
+                    let grab = mouse[axis] - bar.thumb.point()[axis];
+                    self.drag = Some(Drag { axis, grab });
+                } else if bar.track.contains(mouse) {
+                    let page = viewport.size()[axis];
+                    if mouse[axis] < bar.thumb.point()[axis] {
+                        self.offset[axis] -= page;
+                    } else {
+                        self.offset[axis] += page;
+                    }
+                }
+            }
+        }
+        if let Some(Drag { axis, grab }) = self.drag {
+            if !input.is_mouse_button_down(MouseButton::Left) {
+                self.drag = None;
+            } else if let Some(bar) = bars[axis] {
+                let free = bar.track.size()[axis] - bar.thumb.size()[axis];
+                let start = mouse[axis] - grab - bar.track.point()[axis];
+                self.offset[axis] = if free > 0.0 {
+                    start / free * max[axis]
+                } else {
+                    0.0
+                };
+            }
+        }
+        self.offset = self.offset.clamp(SizeInPixels2d::ZERO, max);
+        self.offset != previous
+    }
+
+    /// Where the children can be clicked: nowhere while a scrollbar is being used.
+    pub fn input_clip(
+        &self,
+        viewport: Rect,
+        extent: SizeInPixels2d,
+        input: &dyn InputTrait,
+    ) -> Rect {
+        let mouse = input.mouse_position();
+        let on_scrollbar = scrollbars(viewport, extent, self.offset)
+            .iter()
+            .flatten()
+            .any(|bar| bar.track.contains(mouse));
+        if self.is_dragging() || on_scrollbar {
+            to_rect(viewport.point(), SizeInPixels2d::ZERO)
+        } else {
+            viewport
+        }
+    }
+
+    pub fn render_scrollbars(&self, viewport: Rect, extent: SizeInPixels2d, coloring: &Coloring) {
+        for bar in scrollbars(viewport, extent, self.offset).iter().flatten() {
+            let thumb_style = if self.drag.is_some_and(|drag| drag.axis == bar.axis) {
+                &coloring.pressed
+            } else {
+                &coloring.at_rest
+            };
+            draw_rect(bar.track, coloring.at_rest.bg_color);
+            draw_rect(
+                add_contour(bar.thumb, vec2(-2.0, -2.0)),
+                thumb_style.border_color,
+            );
+        }
+    }
+}
+
+/// How far the content can be scrolled. A scrollbar covers the end of the content in the other
+/// axis, so the content can be scrolled a bit further to see it.
+pub fn max_offset(viewport: SizeInPixels2d, extent: SizeInPixels2d) -> SizeInPixels2d {
+    let overflow = (extent - viewport).max(SizeInPixels2d::ZERO);
+    let covered = |other: Pixels| {
+        if other > 0.0 {
+            SCROLLBAR_THICKNESS
+        } else {
+            0.0
+        }
+    };
+    vec2(
+        if overflow.x > 0.0 {
+            overflow.x + covered(overflow.y)
+        } else {
+            0.0
+        },
+        if overflow.y > 0.0 {
+            overflow.y + covered(overflow.x)
+        } else {
+            0.0
+        },
+    )
+}
+
+/// The scrollbars of the axes where the content doesn't fit, drawn over the viewport at the
+/// bottom and right edges. Indexed by axis.
+pub fn scrollbars(
+    viewport: Rect,
+    extent: SizeInPixels2d,
+    offset: SizeInPixels2d,
+) -> [Option<Scrollbar>; 2] {
+    let max = max_offset(viewport.size(), extent);
+    let shown = [max.x > 0.0, max.y > 0.0];
+    let corner = |axis: usize| {
+        if shown[axis] {
+            SCROLLBAR_THICKNESS
+        } else {
+            0.0
+        }
+    };
+    let tracks = [
+        Rect::new(
+            viewport.x,
+            viewport.bottom() - SCROLLBAR_THICKNESS,
+            viewport.w - corner(1),
+            SCROLLBAR_THICKNESS,
+        ),
+        Rect::new(
+            viewport.right() - SCROLLBAR_THICKNESS,
+            viewport.y,
+            SCROLLBAR_THICKNESS,
+            viewport.h - corner(0),
+        ),
+    ];
+    let bar = |axis: usize| {
+        let track = tracks[axis];
+        let track_length = track.size()[axis];
+        let visible = viewport.size()[axis];
+        let length = (track_length * visible / (visible + max[axis]))
+            .max(SCROLLBAR_THICKNESS)
+            .min(track_length);
+        let mut start = track.point();
+        start[axis] += (track_length - length) * offset[axis] / max[axis];
+        let mut size = track.size();
+        size[axis] = length;
+        Scrollbar {
+            axis,
+            track,
+            thumb: to_rect(start, size),
+        }
+    };
+    [shown[0].then(|| bar(0)), shown[1].then(|| bar(1))]
+}
+
+/// Size of the content of a scroll container whose viewport is `viewport`, given the rects of its
+/// children (including their margins) as they are positioned with `offset`. Never smaller than
+/// the viewport.
+pub fn content_extent(
+    viewport: Rect,
+    offset: SizeInPixels2d,
+    pad: Pad,
+    margined_children: impl IntoIterator<Item = Rect>,
+) -> SizeInPixels2d {
+    let origin: PositionInPixels2d = viewport.point() - offset;
+    margined_children
+        .into_iter()
+        .map(|rect| rect.point() + rect.size() - origin + pad.end())
+        .fold(viewport.size(), SizeInPixels2d::max)
+}
+
+/// A panel with a [`ScrollState`]. Give it a [`crate::lazy::Size`] other than `Fit` (or a
+/// `max_size`) so that it's smaller than its children.
+pub struct Scroll {
+    pub panel: WidgetData<()>,
+    pub state: ScrollState,
+    pub input: Box<dyn InputTrait>,
+}
+
+impl Scroll {
+    pub fn new(style: Style, children: Widgets) -> Self {
+        Self::new_generic(style, children, Box::new(InputMacroquad))
+    }
+    pub fn new_generic(style: Style, children: Widgets, input: Box<dyn InputTrait>) -> Self {
+        Self {
+            panel: WidgetData::container(style, children),
+            state: ScrollState::default(),
+            input,
+        }
+    }
+    pub fn content_extent(&self) -> SizeInPixels2d {
+        let children = self.children();
+        let margined = children
+            .iter()
+            .filter(|child| child.style().visible)
+            .map(|child| add_contour(child.rect(), child.style().margin));
+        content_extent(self.rect(), self.state.offset, self.style().pad, margined)
+    }
+}
+
+impl WidgetTrait for Scroll {
+    fn size(&self) -> SizeInPixels2d {
+        self.panel.size()
+    }
+    fn pos(&self) -> PositionInPixels2d {
+        self.panel.pos()
+    }
+    fn set_pos(&mut self, position: PositionInPixels2d) {
+        self.panel.set_pos(position)
+    }
+    fn set_size(&mut self, size: SizeInPixels2d) {
+        self.panel.set_size(size)
+    }
+    fn content_offset(&self) -> SizeInPixels2d {
+        self.state.offset
+    }
+    fn layout_state(&self) -> LayoutState {
+        self.panel.layout_state()
+    }
+    fn set_layout_state(&mut self, state: LayoutState) {
+        self.panel.set_layout_state(state)
+    }
+    fn respond(&mut self, available: SizeInPixels2d) -> bool {
+        self.panel.respond(available)
+    }
+    fn kind(&self) -> &'static str {
+        "Scroll"
+    }
+    fn clips_children(&self) -> bool {
+        true
+    }
+    fn style(&self) -> &Style {
+        self.panel.style()
+    }
+    fn children_mut(&mut self) -> WidgetsViewMut<'_> {
+        self.panel.children_mut()
+    }
+    fn children(&self) -> WidgetsView<'_> {
+        self.panel.children()
+    }
+}
+
+impl Interactable for Scroll {
+    fn interact(&mut self) -> Vec<Box<dyn Any>> {
+        let viewport = self.rect();
+        let extent = self.content_extent();
+        if self.state.update(viewport, extent, self.input.as_ref()) {
+            let margin = self.style().margin.start();
+            self.mark_dirty(Dirty::Position);
+            set_positions(self, Anchor::top_left_v(viewport.point() - margin));
+        }
+        let input_clip = self.state.input_clip(viewport, extent, self.input.as_ref());
+        clip::with_input_clip(input_clip, || {
+            let mut interactions = Vec::new();
+            for child in self.children_mut() {
+                if child.style().visible {
+                    interactions.extend(child.interact());
+                }
+            }
+            interactions
+        })
+    }
+}
+
+impl Renderable for Scroll {
+    fn render_interactive(&self, _interaction: Interaction) {
+        self.render()
+    }
+    fn render(&self) {
+        let viewport = self.rect();
+        draw_rect(viewport, self.style().coloring.at_rest.bg_color);
+        clip::with_render_clip(viewport, || {
+            for child in self.children() {
+                if child.style().visible {
+                    child.render();
+                }
+            }
+        });
+        self.state
+            .render_scrollbars(viewport, self.content_extent(), &self.style().coloring);
+        if unsafe { DEBUG_WIDGETS } {
+            draw_debug_widget(&self.panel);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lazy::{compute_layout, Size};
+    use crate::testing::{MockInput, MockState};
+    use crate::widgets::anchor::{Horizontal, Layout, Vertical};
+
+    fn leaf(w: f32, h: f32) -> Box<dyn crate::lazy::RenderableWidget> {
+        let style = Style {
+            size: Size::Fixed { w, h },
+            ..Default::default()
+        };
+        Box::new(crate::lazy::panel::Panel::<()>::leaf(style))
+    }
+
+    #[test]
+    fn test_scroll() {
+        let input = MockInput::default();
+        let style = Style {
+            size: Size::Fixed { w: 100.0, h: 100.0 },
+            pad: Pad::new_symmetric(0.0),
+            ..Default::default()
+        };
+        let children = vec![leaf(80.0, 150.0), leaf(80.0, 150.0)];
+        let mut scroll = Scroll::new_generic(style, children, InputTrait::clone(&input));
+        let screen = Rect::new(0.0, 0.0, 800.0, 600.0);
+        let layout = Layout::vertical(Vertical::Bottom, Horizontal::Left);
+        compute_layout(&mut scroll, screen, layout);
+        assert_eq!(scroll.content_extent(), vec2(100.0, 300.0));
+        let bars = scrollbars(scroll.rect(), scroll.content_extent(), scroll.state.offset);
+        assert_eq!(bars[0], None);
+        let bar = bars[1].unwrap();
+        assert_eq!(bar.thumb, Rect::new(88.0, 0.0, 12.0, 100.0 / 3.0));
+
+        let frame = |mouse: MockState, scroll: &mut Scroll| {
+            input.set(mouse);
+            scroll.interact();
+            scroll.state.offset.y
+        };
+        let at = |x, y| MockState {
+            position: vec2(x, y),
+            ..Default::default()
+        };
+        let wheel = MockState {
+            wheel: vec2(0.0, -1.0),
+            ..at(50.0, 50.0)
+        };
+        assert_eq!(frame(wheel, &mut scroll), WHEEL_STEP);
+        assert_eq!(scroll.children()[0].pos(), vec2(10.0, -WHEEL_STEP));
+        assert_eq!(
+            frame(at(50.0, 50.0), &mut scroll),
+            WHEEL_STEP,
+            "kept across frames"
+        );
+
+        let outside = MockState {
+            wheel: vec2(0.0, -1.0),
+            ..at(500.0, 50.0)
+        };
+        assert_eq!(frame(outside, &mut scroll), WHEEL_STEP);
+
+        let track_click = MockState {
+            pressed: true,
+            down: true,
+            ..at(95.0, 95.0)
+        };
+        assert_eq!(frame(track_click, &mut scroll), WHEEL_STEP + 100.0);
+
+        let thumb_y = scrollbars(scroll.rect(), scroll.content_extent(), scroll.state.offset)[1]
+            .unwrap()
+            .thumb
+            .y;
+        let grab = MockState {
+            pressed: true,
+            down: true,
+            ..at(95.0, thumb_y + 1.0)
+        };
+        frame(grab, &mut scroll);
+        assert!(scroll.state.is_dragging());
+        let drag_to_end = MockState {
+            down: true,
+            ..at(95.0, 1000.0)
+        };
+        assert_eq!(frame(drag_to_end, &mut scroll), 200.0);
+        assert_eq!(frame(at(95.0, 1000.0), &mut scroll), 200.0);
+        assert!(!scroll.state.is_dragging());
+        assert_eq!(scroll.children()[1].rect().bottom(), 100.0);
+    }
+}
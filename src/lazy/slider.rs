@@ -1,8 +1,11 @@
+use crate::clip;
 use crate::draw::{draw_rect, draw_rect_lines};
 use crate::input::input_macroquad::InputMacroquad;
 use crate::input::input_trait::InputTrait;
+use crate::lazy::dump::{describe, Describe};
 use crate::lazy::{
-    draw_debug_widget, Interactable, Renderable, Style, WidgetData, WidgetTrait, DEBUG_WIDGETS,
+    draw_debug_widget, Interactable, LayoutState, Renderable, Style, WidgetData, WidgetTrait,
+    DEBUG_WIDGETS,
 };
 use crate::widgets::Interaction;
 use crate::SizeInPixels2d;
@@ -25,6 +28,12 @@ pub struct SliderBase {
     pub render_slider: RenderSlider,
 }
 
+impl Describe for SliderBase {
+    fn kind(&self) -> &'static str {
+        "Slider"
+    }
+}
+
 impl Slider {
     pub fn new(style: Style, min: f32, max: f32, current: f32) -> Self {
         let font_size = style.font_size;
@@ -40,7 +49,7 @@ impl Slider {
         )
     }
     pub fn new_generic(
-        style: Style,
+        mut style: Style,
         min: f32,
         max: f32,
         current: f32,
@@ -48,6 +57,7 @@ impl Slider {
         input: Box<dyn InputTrait>,
         render_slider: RenderSlider,
     ) -> Self {
+        style.min_size = style.min_size.max(min_size);
         let custom = SliderBase {
             min,
             max,
@@ -60,6 +70,10 @@ impl Slider {
         Self {
             pos: Default::default(),
             size: Some(min_size),
+            content_size: Some(min_size),
+            layout_state: LayoutState::default(),
+            measure: None,
+            describe: Some(describe),
             style,
             custom,
             children: vec![],
@@ -84,7 +98,7 @@ impl Interactable for Slider {
         let handle_width = self.handle_width();
         let mouse_coef =
             (mouse_pos - rect.point() - 0.5 * handle_width) / (rect.size() - handle_width);
-        let (interaction, render_pos) = if rect.contains(mouse_pos) {
+        let (interaction, render_pos) = if clip::clip_rect(rect).contains(mouse_pos) {
             if input.is_mouse_button_down(MouseButton::Left) {
                 (Interaction::Pressing, mouse_coef.x)
             } else if input.is_mouse_button_released(MouseButton::Left) {
@@ -1,21 +1,56 @@
+use crate::fonts::FontId;
+use crate::lazy::dump::{describe, Describe};
 use crate::lazy::{
-    add_contour, draw_debug_widget, Interactable, Renderable, Style, WidgetData, WidgetTrait,
-    DEBUG_WIDGETS,
+    add_contour, draw_debug_widget, Interactable, LayoutState, Pad, Renderable, Size, Style,
+    WidgetData, WidgetTrait, DEBUG_WIDGETS,
+};
+use crate::measure_cache;
+use crate::widgets::text::{
+    draw_text_v, ellipsize, fit_text, AutoFit, FittedText, MeasureText, Pixels,
 };
-use crate::widgets::text::{draw_text_v, MeasureText};
 use crate::widgets::Interaction;
 use crate::SizeInPixels2d;
 use macroquad::math::Vec2;
-use macroquad::prelude::{vec2, Font};
+use macroquad::prelude::vec2;
 
 pub type Text = WidgetData<TextBase>;
 pub type RenderText = fn(widget: &Text, interaction: Interaction);
 
 pub struct TextBase {
     pub text: String,
+    /// `text` and the font size chosen to fit a fixed size, see [`Style::auto_fit`]
+    pub fitted: Option<FittedText>,
+    /// the text cut with "..." to fit the width it was given, see [`Style::ellipsis`]
+    pub shown: Option<String>,
     pub reference_height: f32,
+    pub measure_text: MeasureText,
     pub render_text: RenderText,
 }
+
+impl Describe for TextBase {
+    fn kind(&self) -> &'static str {
+        "Text"
+    }
+    fn text(&self) -> Option<&str> {
+        Some(self.shown())
+    }
+}
+impl TextBase {
+    /// The text as rendered, which might be cut.
+    pub fn shown(&self) -> &str {
+        match (&self.shown, &self.fitted) {
+            (Some(shown), _) => shown,
+            (None, Some(fitted)) => &fitted.text,
+            (None, None) => &self.text,
+        }
+    }
+    /// The font size it's rendered with, which is `style_font_size` unless it was fitted.
+    pub fn font_size(&self, style_font_size: f32) -> f32 {
+        self.fitted
+            .as_ref()
+            .map_or(style_font_size, |fitted| fitted.font_size)
+    }
+}
 impl Text {
     pub fn new(style: &Style, text: &str) -> Self {
         Self::new_generic(style, text, macroquad::text::measure_text, render_text)
@@ -26,21 +61,65 @@ impl Text {
         measure_text: MeasureText,
         render_text: RenderText,
     ) -> Self {
-        let mut size = size_text(text, &style, measure_text);
-        let reference_height = size.y;
-        size += 2.0 * style.pad.vec2();
         let custom = TextBase {
             text: text.to_string(),
-            reference_height,
+            fitted: None,
+            shown: None,
+            reference_height: 0.0,
+            measure_text,
             render_text,
         };
-        Self {
+        let mut text = Self {
             pos: Default::default(),
-            size: Some(size),
+            size: None,
+            content_size: None,
+            layout_state: LayoutState::default(),
+            measure: Some(Self::measure),
+            describe: Some(describe),
             style: style.clone(),
             custom,
             children: Vec::new(),
+        };
+        text.measure();
+        text
+    }
+    fn measure(&mut self) {
+        let style = &self.style;
+        let measure_text = self.custom.measure_text;
+        self.custom.fitted = fit_fixed_size(
+            &self.custom.text,
+            style.size,
+            style.pad,
+            style.auto_fit,
+            style.font,
+            measure_text,
+        );
+        let font_size = self.custom.font_size(style.font_size);
+        let text = match &self.custom.fitted {
+            Some(fitted) => &fitted.text,
+            None => &self.custom.text,
+        };
+        let mut size = size_text_generic(text, measure_text, style.font, font_size);
+        self.custom.shown = None;
+        if let Some((shown, shown_size)) = limit_text_width(
+            text,
+            size,
+            self.layout_state.width_limit,
+            style.pad,
+            style.font,
+            font_size,
+            measure_text,
+        ) {
+            self.custom.shown = Some(shown);
+            size = shown_size;
         }
+        self.custom.reference_height = size.y;
+        size += style.pad.total();
+        if let Size::Fixed { w, h } = style.size {
+            size = vec2(w, h);
+        }
+        self.size = Some(size);
+        self.content_size = Some(size);
     }
     pub fn render(&self) {
         self.render_interactive(Interaction::None)
@@ -53,27 +132,65 @@ impl Renderable for Text {
 }
 impl Interactable for Text {}
 
+/// If the widget has a fixed size and allows changing the font size, returns the text and font
+/// size that fit inside the pad.
+pub fn fit_fixed_size(
+    text: &str,
+    size: Size,
+    pad: Pad,
+    auto_fit: Option<AutoFit>,
+    font: FontId,
+    measure_text: MeasureText,
+) -> Option<FittedText> {
+    if let (Some(auto_fit), Size::Fixed { w, h }) = (auto_fit, size) {
+        let available = vec2(w, h) - pad.total();
+        Some(fit_text(text, available, auto_fit, font, measure_text))
+    } else {
+        None
+    }
+}
+/// If the text of size `size` doesn't fit in `width_limit` (pad included), returns it cut with
+/// "..." and its new size.
+pub fn limit_text_width(
+    text: &str,
+    size: SizeInPixels2d,
+    width_limit: Option<Pixels>,
+    pad: Pad,
+    font: FontId,
+    font_size: f32,
+    measure_text: MeasureText,
+) -> Option<(String, SizeInPixels2d)> {
+    let max_width = width_limit? - pad.total().x;
+    if size.x <= max_width {
+        return None;
+    }
+    let shown = ellipsize(text, max_width.max(0.0), font, font_size, measure_text);
+    let shown_size = size_text_generic(&shown, measure_text, font, font_size);
+    Some((shown, vec2(shown_size.x, size.y)))
+}
 pub fn size_text(text: &str, style: &Style, measure_text: MeasureText) -> SizeInPixels2d {
-    size_text_generic(text, measure_text, style.font.as_ref(), style.font_size)
+    size_text_generic(text, measure_text, style.font, style.font_size)
 }
 pub fn size_text_generic(
     text: &str,
     measure_text: MeasureText,
-    font: Option<&Font>,
+    font: FontId,
     font_size: f32,
 ) -> SizeInPixels2d {
     // font_size doesn't seem to be in pixels across fonts
-    let reference_size = measure_text("Odp", font, font_size as u16, 1.0);
+    let reference_size =
+        measure_cache::measure_text("Odp", font, font_size as u16, 1.0, measure_text);
     let reference_height = reference_size.height;
-    let text_dimensions = measure_text(text, font, font_size as u16, 1.0);
+    let text_dimensions =
+        measure_cache::measure_text(text, font, font_size as u16, 1.0, measure_text);
 
     let size = Vec2::new(text_dimensions.width.round(), reference_height.round());
     size
 }
 pub fn render_text(widget: &Text, interaction: Interaction) {
     let reference_height = widget.custom.reference_height;
-    let text = &widget.custom.text;
-    let rect_pad = add_contour(widget.rect(), -widget.style.pad.vec2());
+    let text = widget.custom.shown();
+    let rect_pad = add_contour(widget.rect(), -widget.style.pad);
     if unsafe { DEBUG_WIDGETS } {
         draw_debug_widget(widget);
     }
@@ -89,9 +206,9 @@ pub fn render_text(widget: &Text, interaction: Interaction) {
     draw_text_v(
         text,
         vec2(x, y),
-        widget.style.font_size,
+        widget.custom.font_size(widget.style.font_size),
         &widget.style.coloring.choose(interaction),
-        widget.style.font.as_ref(),
+        widget.style.font,
     );
 }
 
@@ -107,3 +224,50 @@ fn print_debug_pos(x: f32, y: f32) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lazy::responsive::Breakpoint;
+    use crate::lazy::{compute_layout, set_sizes, Dirty, Pad};
+    use crate::testing::mock_measure;
+    use crate::widgets::text::AutoFit;
+    use macroquad::prelude::{vec2, Rect};
+
+    #[test]
+    fn test_auto_fit_keeps_source_text() {
+        let style = Style {
+            pad: Pad::new_symmetric(0.0),
+            auto_fit: Some(AutoFit::new(10.0, 20.0)),
+            size: Size::Fixed { w: 40.0, h: 30.0 },
+            ..Default::default()
+        };
+        let mut text = Text::new_generic(&style, "abcdef", mock_measure, render_text);
+        set_sizes(&mut text);
+        assert_eq!(text.custom.shown(), "ab...");
+        assert_eq!(text.custom.text, "abcdef");
+        assert_eq!(text.custom.font_size(style.font_size), 10.0);
+        assert_eq!(text.style.font_size, style.font_size);
+
+        text.style_mut().size = Size::Fixed { w: 100.0, h: 30.0 };
+        set_sizes(&mut text);
+        assert_eq!(text.custom.shown(), "abcdef");
+        assert_eq!(text.custom.font_size(style.font_size), 20.0);
+    }
+
+    #[test]
+    fn test_auto_fit_with_breakpoints_stays_clean() {
+        let mut style = Style {
+            auto_fit: Some(AutoFit::new(10.0, 20.0)),
+            size: Size::Fixed { w: 100.0, h: 60.0 },
+            ..Default::default()
+        };
+        style.breakpoints = vec![Breakpoint::new(vec2(0.0, 0.0), style.variant())];
+        let mut text = Text::new_generic(&style, "abcdef", mock_measure, render_text);
+        let screen = Rect::new(0.0, 0.0, 400.0, 300.0);
+        compute_layout(&mut text, screen, style.layout);
+        compute_layout(&mut text, screen, style.layout);
+        assert!(!text.respond(screen.size()));
+        assert_eq!(text.layout_state.dirty, Dirty::Clean);
+    }
+}
@@ -1,15 +1,24 @@
 use macroquad::prelude::Vec2;
 
+pub mod clip;
 pub mod draw;
+pub mod fonts;
 pub mod fps;
+pub mod i18n;
 pub mod lazy;
+pub mod measure_cache;
 pub mod resource_loader;
+#[cfg(test)]
+mod testing;
 pub mod texture_loader;
 pub mod widgets;
 
 pub mod elm {
     pub mod button;
+    pub mod checkbox;
     pub mod container;
+    pub mod radio_group;
+    pub mod scroll;
     pub mod slider;
     pub mod style;
     pub mod text;
@@ -0,0 +1,242 @@
+//! Cache of text measurements, shared by the `widgets`, `lazy` and `elm` APIs.
+//!
+//! Measuring text requires iterating the glyphs of the font, and UIs tend to measure the same
+//! strings every frame (e.g. [`crate::widgets::text::draw_text_lines`]), so the results are kept
+//! here, keyed by text, font and size. Each cache has its own measuring function, and the shared
+//! one, used through [`measure_text_cached`], measures with [`macroquad::prelude::measure_text`].
+//!
+//! The memory is bounded: there are 2 generations of entries, and when the newest is full, the
+//! oldest is dropped. Entries used from the oldest generation are moved back to the newest one.
+
+use crate::fonts;
+use crate::fonts::FontId;
+use crate::widgets::text::MeasureText;
+use macroquad::prelude::TextDimensions;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+pub const DEFAULT_CAPACITY: usize = 2048;
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+struct MeasureKey {
+    font: FontId,
+    font_size: u16,
+    font_scale_bits: u32,
+}
+
+type Generation = HashMap<MeasureKey, HashMap<String, TextDimensions>>;
+
+pub struct MeasureCache {
+    newest: Generation,
+    oldest: Generation,
+    newest_len: usize,
+    oldest_len: usize,
+    capacity: usize,
+    measure_text: MeasureText,
+}
+
+impl Default for MeasureCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY, macroquad::prelude::measure_text)
+    }
+}
+
+impl MeasureCache {
+    /// At most `capacity` measurements of `measure_text` will be stored.
+    pub fn new(capacity: usize, measure_text: MeasureText) -> Self {
+        Self {
+            newest: HashMap::new(),
+            oldest: HashMap::new(),
+            newest_len: 0,
+            oldest_len: 0,
+            capacity,
+            measure_text,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.newest_len + self.oldest_len
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    pub fn clear(&mut self) {
+        *self = Self::new(self.capacity, self.measure_text);
+    }
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.clear();
+    }
+
+    pub fn measure_text(
+        &mut self,
+        text: &str,
+        font: FontId,
+        font_size: u16,
+        font_scale: f32,
+    ) -> TextDimensions {
+        let key = MeasureKey {
+            font,
+            font_size,
+            font_scale_bits: font_scale.to_bits(),
+        };
+        if let Some(dimensions) = self.newest.get(&key).and_then(|texts| texts.get(text)) {
+            return *dimensions;
+        }
+        let dimensions = if let Some(dimensions) = self
+            .oldest
+            .get_mut(&key)
+            .and_then(|texts| texts.remove(text))
+        {
+            self.oldest_len -= 1;
+            dimensions
+        } else {
+            fonts::measure_text(text, font, font_size, font_scale, self.measure_text)
+        };
+        self.insert(key, text, dimensions);
+        dimensions
+    }
+
+    fn insert(&mut self, key: MeasureKey, text: &str, dimensions: TextDimensions) {
+        if self.capacity == 0 {
+            return;
+        }
+        // with a capacity of 1 there is no room for an oldest generation
+        let generation_capacity = (self.capacity / 2).max(1);
+        if self.newest_len >= generation_capacity {
+            if self.capacity == 1 {
+                self.newest.clear();
+                self.newest_len = 0;
+            }
+            self.oldest = std::mem::take(&mut self.newest);
+            self.oldest_len = self.newest_len;
+            self.newest_len = 0;
+        }
+        self.newest
+            .entry(key)
+            .or_default()
+            .insert(text.to_string(), dimensions);
+        self.newest_len += 1;
+    }
+}
+
+thread_local! {
+    static MEASURE_CACHE: RefCell<MeasureCache> = RefCell::new(MeasureCache::default());
+}
+
+/// Same as [`fonts::measure_text`] with [`macroquad::prelude::measure_text`], but reusing the
+/// previous measurements.
+pub fn measure_text_cached(
+    text: &str,
+    font: FontId,
+    font_size: u16,
+    font_scale: f32,
+) -> TextDimensions {
+    MEASURE_CACHE.with(|cache| {
+        cache
+            .borrow_mut()
+            .measure_text(text, font, font_size, font_scale)
+    })
+}
+
+/// Same as [`fonts::measure_text`], measuring every time. Use [`measure_text_cached`] or a
+/// [`MeasureCache`] of your own to reuse measurements.
+pub fn measure_text(
+    text: &str,
+    font: FontId,
+    font_size: u16,
+    font_scale: f32,
+    measure_text: MeasureText,
+) -> TextDimensions {
+    fonts::measure_text(text, font, font_size, font_scale, measure_text)
+}
+
+/// Needed if a font changes after some text was measured with it, e.g. its fallbacks.
+pub fn clear() {
+    MEASURE_CACHE.with(|cache| cache.borrow_mut().clear())
+}
+
+pub fn set_capacity(capacity: usize) {
+    MEASURE_CACHE.with(|cache| cache.borrow_mut().set_capacity(capacity))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fonts::DEFAULT_FONT;
+    use macroquad::prelude::Font;
+    use std::cell::Cell;
+
+    thread_local! {
+        static MEASUREMENTS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    fn counting_measure(
+        text: &str,
+        _font: Option<&Font>,
+        font_size: u16,
+        _font_scale: f32,
+    ) -> TextDimensions {
+        MEASUREMENTS.with(|count| count.set(count.get() + 1));
+        TextDimensions {
+            width: text.len() as f32 * font_size as f32,
+            height: font_size as f32,
+            offset_y: font_size as f32,
+        }
+    }
+    fn measurements() -> usize {
+        MEASUREMENTS.with(|count| count.get())
+    }
+
+    #[test]
+    fn test_repeated_measures_are_cached() {
+        let mut cache = MeasureCache::new(10, counting_measure);
+        let first = cache.measure_text("abc", DEFAULT_FONT, 10, 1.0);
+        let second = cache.measure_text("abc", DEFAULT_FONT, 10, 1.0);
+        assert_eq!(measurements(), 1);
+        assert_eq!(first.width, 30.0);
+        assert_eq!(second.width, 30.0);
+
+        let bigger = cache.measure_text("abc", DEFAULT_FONT, 20, 1.0);
+        assert_eq!(measurements(), 2);
+        assert_eq!(bigger.width, 60.0);
+    }
+
+    #[test]
+    fn test_capacity_is_bounded() {
+        let mut cache = MeasureCache::new(4, counting_measure);
+        for i in 0..100 {
+            cache.measure_text(&i.to_string(), DEFAULT_FONT, 10, 1.0);
+            assert!(cache.len() <= 4);
+        }
+        let before = measurements();
+        cache.measure_text("99", DEFAULT_FONT, 10, 1.0);
+        assert_eq!(measurements(), before);
+        cache.measure_text("0", DEFAULT_FONT, 10, 1.0);
+        assert_eq!(measurements(), before + 1);
+    }
+
+    #[test]
+    fn test_odd_capacity_is_bounded() {
+        for capacity in [1, 3, 5] {
+            let mut cache = MeasureCache::new(capacity, counting_measure);
+            for i in 0..20 {
+                cache.measure_text(&i.to_string(), DEFAULT_FONT, 10, 1.0);
+                assert!(cache.len() <= capacity);
+            }
+            let before = measurements();
+            cache.measure_text("19", DEFAULT_FONT, 10, 1.0);
+            assert_eq!(measurements(), before);
+        }
+    }
+
+    #[test]
+    fn test_generic_measures_skip_the_shared_cache() {
+        let before = measurements();
+        for _ in 0..2 {
+            let dimensions = measure_text("abc", DEFAULT_FONT, 10, 1.0, counting_measure);
+            assert_eq!(dimensions.width, 30.0);
+        }
+        assert_eq!(measurements(), before + 2);
+    }
+}
@@ -0,0 +1,104 @@
+//! Fakes for the tests: an input that the test drives, and a way to measure texts without fonts.
+
+use crate::input::input_trait::InputTrait;
+use crate::PositionInPixels2d;
+use macroquad::math::vec2;
+use macroquad::prelude::{Font, KeyCode, MouseButton, TextDimensions};
+use std::cell::Cell;
+use std::rc::Rc;
+
+/// What the [`MockInput`] reports until it's changed.
+#[derive(Copy, Clone)]
+pub struct MockState {
+    pub position: PositionInPixels2d,
+    pub wheel: PositionInPixels2d,
+    pub down: bool,
+    pub pressed: bool,
+    pub released: bool,
+    pub key: Option<KeyCode>,
+}
+
+impl Default for MockState {
+    /// Nothing happens, and the mouse is away from the widgets.
+    fn default() -> Self {
+        Self {
+            position: vec2(-1.0, -1.0),
+            wheel: PositionInPixels2d::ZERO,
+            down: false,
+            pressed: false,
+            released: false,
+            key: None,
+        }
+    }
+}
+
+/// Clones share the state, so the test keeps one and gives a clone to the widget.
+#[derive(Clone, Default)]
+pub struct MockInput {
+    state: Rc<Cell<MockState>>,
+}
+
+impl MockInput {
+    pub fn set(&self, state: MockState) {
+        self.state.set(state);
+    }
+    /// The mouse is released at `position`, which is what widgets take as a click.
+    pub fn click(&self, position: PositionInPixels2d) {
+        self.set(MockState {
+            position,
+            released: true,
+            ..Default::default()
+        });
+    }
+    pub fn press_key(&self, key: KeyCode) {
+        self.set(MockState {
+            key: Some(key),
+            ..Default::default()
+        });
+    }
+    pub fn clear(&self) {
+        self.set(MockState::default());
+    }
+}
+
+impl InputTrait for MockInput {
+    fn is_key_down(&self, key: KeyCode) -> bool {
+        self.state.get().key == Some(key)
+    }
+    fn is_key_pressed(&self, key: KeyCode) -> bool {
+        self.state.get().key == Some(key)
+    }
+    fn is_mouse_button_down(&self, _button: MouseButton) -> bool {
+        self.state.get().down
+    }
+    fn is_mouse_button_pressed(&self, _button: MouseButton) -> bool {
+        self.state.get().pressed
+    }
+    fn is_mouse_button_released(&self, _button: MouseButton) -> bool {
+        self.state.get().released
+    }
+    fn mouse_position(&self) -> PositionInPixels2d {
+        self.state.get().position
+    }
+    fn mouse_wheel(&self) -> PositionInPixels2d {
+        self.state.get().wheel
+    }
+    fn clone(&self) -> Box<dyn InputTrait> {
+        Box::new(Clone::clone(self))
+    }
+}
+
+/// Each char is 3/4 of the font size wide, and each line is as tall as the font size.
+pub fn mock_measure(
+    text: &str,
+    _font: Option<&Font>,
+    font_size: u16,
+    font_scale: f32,
+) -> TextDimensions {
+    let font_size = font_size as f32 * font_scale;
+    TextDimensions {
+        width: 0.75 * font_size * text.chars().count() as f32,
+        height: font_size,
+        offset_y: 0.75 * font_size,
+    }
+}
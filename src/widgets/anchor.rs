@@ -1,6 +1,6 @@
 use crate::{PositionInPixels2d, SizeInPixels2d};
 use macroquad::math::{Rect, Vec2};
-use macroquad::prelude::vec2;
+use macroquad::prelude::{screen_height, screen_width, vec2};
 
 /// An Anchor helps you define positions for rectangles.
 /// All f32 values are in pixels units, e.g. (800.0, 600.0). Top left is (0.0, 0.0).
@@ -53,8 +53,13 @@ pub enum Spot {
 }
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum Sense {
+    /// rightwards or downwards
     Forwards,
+    /// leftwards or upwards
     Backwards,
+    /// the first widget goes in the middle and the rest go forwards, like a [`Layout`] with a
+    /// `Center` direction
+    Centered,
 }
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum Direction {
@@ -72,12 +77,62 @@ pub enum Direction {
 //     },
 // }
 
+/// Same as [`Layout`], but without mixing horizontal and vertical enums: `direction` is the axis
+/// where widgets follow each other, `sense` which way they go along it, and `alignment` where
+/// they go in the other axis. Anything that takes a `Layout` also takes a `LayoutV2`, and the
+/// conversions between them are lossless in both ways.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub struct LayoutV2 {
     pub direction: Direction,
     pub sense: Sense,
     pub alignment: Spot,
 }
 
+impl LayoutV2 {
+    pub fn new(direction: Direction, sense: Sense, alignment: Spot) -> Self {
+        Self {
+            direction,
+            sense,
+            alignment,
+        }
+    }
+    pub fn horizontal(sense: Sense, alignment: Spot) -> Self {
+        Self::new(Direction::Horizontal, sense, alignment)
+    }
+    pub fn vertical(sense: Sense, alignment: Spot) -> Self {
+        Self::new(Direction::Vertical, sense, alignment)
+    }
+}
+
+impl From<Layout> for LayoutV2 {
+    fn from(layout: Layout) -> Self {
+        let direction = match layout {
+            Layout::Horizontal { .. } => Direction::Horizontal,
+            Layout::Vertical { .. } => Direction::Vertical,
+        };
+        let sense = match layout.get_direction() {
+            Spot::Beginning => Sense::Backwards,
+            Spot::Center => Sense::Centered,
+            Spot::End => Sense::Forwards,
+        };
+        Self::new(direction, sense, layout.get_alignment())
+    }
+}
+
+impl From<LayoutV2> for Layout {
+    fn from(layout: LayoutV2) -> Self {
+        let direction = match layout.sense {
+            Sense::Backwards => Spot::Beginning,
+            Sense::Centered => Spot::Center,
+            Sense::Forwards => Spot::End,
+        };
+        match layout.direction {
+            Direction::Horizontal => Layout::horizontal(direction.into(), layout.alignment.into()),
+            Direction::Vertical => Layout::vertical(direction.into(), layout.alignment.into()),
+        }
+    }
+}
+
 use Horizontal as H;
 use Vertical as V;
 
@@ -189,8 +244,8 @@ impl Anchor {
         Rect::new(pos.x, pos.y, size.x, size.y)
     }
 
-    pub fn next_to(other: Rect, layout: Layout, margin: f32) -> Anchor {
-        match layout {
+    pub fn next_to<L: Into<Layout>>(other: Rect, layout: L, margin: f32) -> Anchor {
+        match layout.into() {
             Layout::Horizontal {
                 direction,
                 alignment,
@@ -249,8 +304,8 @@ impl Anchor {
     //     Anchor::new(horiz, vert, x, y)
     // }
 
-    pub fn inside(other: Rect, layout: Layout, pad: SizeInPixels2d) -> Anchor {
-        match layout {
+    pub fn inside<L: Into<Layout>>(other: Rect, layout: L, pad: SizeInPixels2d) -> Anchor {
+        match layout.into() {
             Layout::Horizontal {
                 direction,
                 alignment,
@@ -305,6 +360,69 @@ impl Default for Anchor {
         Anchor::top_left(0.0, 0.0)
     }
 }
+
+/// An [`Anchor`] whose point is a fraction of a reference rect plus an offset in pixels, e.g.
+/// "the top right corner of the screen, 10 pixels inwards". It's resolved when used, so that a
+/// HUD stays in place when the window is resized.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RelativeAnchor {
+    horizontal: Horizontal,
+    vertical: Vertical,
+    /// (0.0, 0.0) is the top left of the reference and (1.0, 1.0) its bottom right
+    fraction: Vec2,
+    offset: SizeInPixels2d,
+    /// if None, the screen
+    reference: Option<Rect>,
+}
+
+impl RelativeAnchor {
+    /// Relative to the screen.
+    pub fn new(
+        horizontal: Horizontal,
+        vertical: Vertical,
+        fraction: Vec2,
+        offset: SizeInPixels2d,
+    ) -> Self {
+        Self {
+            horizontal,
+            vertical,
+            fraction,
+            offset,
+            reference: None,
+        }
+    }
+    /// At the same spot of the screen as the alignment, e.g. a top right anchor at the top right
+    /// corner of the screen.
+    pub fn aligned(horizontal: Horizontal, vertical: Vertical, offset: SizeInPixels2d) -> Self {
+        let fraction = vec2(horizontal.fraction(), vertical.fraction());
+        Self::new(horizontal, vertical, fraction, offset)
+    }
+    /// Relative to `reference` instead of the screen.
+    #[must_use]
+    pub fn within(self, reference: Rect) -> Self {
+        Self {
+            reference: Some(reference),
+            ..self
+        }
+    }
+    pub fn resolve(&self, screen: SizeInPixels2d) -> Anchor {
+        let reference = self
+            .reference
+            .unwrap_or(Rect::new(0.0, 0.0, screen.x, screen.y));
+        let position = reference.point() + self.fraction * reference.size() + self.offset;
+        Anchor::new_v(self.horizontal, self.vertical, position)
+    }
+    /// Resolved against the current screen size.
+    pub fn current(&self) -> Anchor {
+        self.resolve(vec2(screen_width(), screen_height()))
+    }
+    pub fn get_top_left_pixel(&self, size: SizeInPixels2d) -> PositionInPixels2d {
+        self.current().get_top_left_pixel(size)
+    }
+    pub fn get_rect(&self, size: SizeInPixels2d) -> Rect {
+        self.current().get_rect(size)
+    }
+}
 impl Layout {
     pub fn vertical(direction: Vertical, alignment: Horizontal) -> Self {
         Self::Vertical {
@@ -382,6 +500,33 @@ impl Layout {
         //     }
         // }
     }
+    /// The layout of a column that starts from a widget placed with `self`: it goes away from the
+    /// alignment side (downwards if centered), and it's aligned to the side where `self` starts.
+    #[must_use]
+    pub fn perpendicular_layout(self) -> Self {
+        match self {
+            Layout::Horizontal {
+                direction,
+                alignment,
+            } => Layout::Vertical {
+                direction: match alignment {
+                    Vertical::Bottom => Vertical::Top,
+                    Vertical::Top | Vertical::Center => Vertical::Bottom,
+                },
+                alignment: direction.opposite(),
+            },
+            Layout::Vertical {
+                direction,
+                alignment,
+            } => Layout::Horizontal {
+                direction: match alignment {
+                    Horizontal::Right => Horizontal::Left,
+                    Horizontal::Left | Horizontal::Center => Horizontal::Right,
+                },
+                alignment: direction.opposite(),
+            },
+        }
+    }
     #[must_use]
     pub fn opposite(self, horiz: Horizontal, vert: Vertical) -> Self {
         match self {
@@ -504,6 +649,14 @@ impl Layout {
     }
 }
 impl Horizontal {
+    /// Where this alignment is along a width, from 0.0 (left) to 1.0 (right).
+    pub fn fraction(self) -> f32 {
+        match self {
+            Self::Left => 0.0,
+            Self::Center => 0.5,
+            Self::Right => 1.0,
+        }
+    }
     pub fn x_pad(self, other: Rect, pad: SizeInPixels2d) -> f32 {
         match self {
             Self::Left => other.left() + pad.x,
@@ -541,6 +694,14 @@ impl Horizontal {
 }
 
 impl Vertical {
+    /// Where this alignment is along a height, from 0.0 (top) to 1.0 (bottom).
+    pub fn fraction(self) -> f32 {
+        match self {
+            Self::Top => 0.0,
+            Self::Center => 0.5,
+            Self::Bottom => 1.0,
+        }
+    }
     pub fn y_pad(self, other: Rect, pad: SizeInPixels2d) -> f32 {
         match self {
             Self::Top => other.top() + pad.y,
@@ -630,3 +791,92 @@ impl Spot {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all_layouts() -> Vec<Layout> {
+        let mut layouts = Vec::new();
+        for h in [H::Left, H::Center, H::Right] {
+            for v in [V::Top, V::Center, V::Bottom] {
+                layouts.push(Layout::horizontal(h, v));
+                layouts.push(Layout::vertical(v, h));
+            }
+        }
+        layouts
+    }
+
+    #[test]
+    fn test_layout_v2_conversion_is_lossless() {
+        for layout in all_layouts() {
+            let v2 = LayoutV2::from(layout);
+            assert_eq!(Layout::from(v2), layout);
+            assert_eq!(LayoutV2::from(Layout::from(v2)), v2);
+        }
+        assert_eq!(
+            LayoutV2::from(Layout::vertical(V::Top, H::Right)),
+            LayoutV2::vertical(Sense::Backwards, Spot::End)
+        );
+    }
+
+    #[test]
+    fn test_layout_v2_anchors() {
+        let other = Rect::new(100.0, 200.0, 50.0, 30.0);
+        let size = vec2(20.0, 10.0);
+        let pad = vec2(3.0, 4.0);
+        let rects = |v2: LayoutV2| {
+            (
+                Anchor::next_to(other, v2, 5.0).get_rect(size),
+                Anchor::inside(other, v2, pad).get_rect(size),
+            )
+        };
+        // to the right, top aligned, and inside from the top left corner
+        assert_eq!(
+            rects(LayoutV2::horizontal(Sense::Forwards, Spot::Beginning)),
+            (
+                Rect::new(155.0, 200.0, 20.0, 10.0),
+                Rect::new(103.0, 204.0, 20.0, 10.0)
+            )
+        );
+        // above, right aligned, and inside from the bottom right corner
+        assert_eq!(
+            rects(LayoutV2::vertical(Sense::Backwards, Spot::End)),
+            (
+                Rect::new(130.0, 185.0, 20.0, 10.0),
+                Rect::new(127.0, 216.0, 20.0, 10.0)
+            )
+        );
+        // to the left, vertically centered, and inside from the middle of the right side
+        assert_eq!(
+            rects(LayoutV2::horizontal(Sense::Backwards, Spot::Center)),
+            (
+                Rect::new(75.0, 210.0, 20.0, 10.0),
+                Rect::new(127.0, 210.0, 20.0, 10.0)
+            )
+        );
+    }
+
+    #[test]
+    fn test_relative_anchor() {
+        let size = vec2(20.0, 10.0);
+        let corner = RelativeAnchor::aligned(H::Right, V::Top, vec2(-5.0, 5.0));
+        let small = vec2(800.0, 600.0);
+        let big = vec2(1000.0, 700.0);
+        assert_eq!(
+            corner.resolve(small).get_top_left_pixel(size),
+            vec2(775.0, 5.0)
+        );
+        assert_eq!(
+            corner.resolve(big).get_top_left_pixel(size),
+            vec2(975.0, 5.0)
+        );
+
+        let third = RelativeAnchor::new(H::Center, V::Center, vec2(1.0 / 3.0, 0.5), Vec2::ZERO);
+        let reference = Rect::new(100.0, 100.0, 300.0, 200.0);
+        assert_eq!(
+            third.within(reference).resolve(big).get_rect(size),
+            Rect::new(190.0, 195.0, 20.0, 10.0)
+        );
+    }
+}
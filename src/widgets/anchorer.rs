@@ -1,30 +1,159 @@
-use crate::widgets::anchor::{Anchor, Layout};
+use crate::widgets::anchor::{Anchor, Horizontal, Layout, Vertical};
 use crate::widgets::button::Button;
 use crate::widgets::text::TextRect;
 use crate::widgets::Widget;
 use crate::PositionInPixels2d;
-use macroquad::math::Rect;
+use macroquad::math::{Rect, Vec2};
 
 pub struct Anchorer {
     current: Rect,
     layout: Layout,
     pad: f32,
+    /// if Some, widgets that would go beyond it start a new line
+    bounds: Option<Rect>,
+    /// bounding rect of the widgets in the current line
+    line: Option<Rect>,
+    /// if Some, lines have at most this many widgets
+    break_every: Option<usize>,
+    /// number of widgets in the current line
+    line_count: usize,
+    /// where the first widget goes, if it doesn't go next to `current`
+    first: Option<Anchor>,
+    /// bounding rect of all the widgets placed
+    placed: Option<Rect>,
+    /// each widget placed, in order
+    cells: Vec<Rect>,
+    /// the cells of each fork joined in the current line, see [`Self::align_rows`]
+    columns: Vec<Vec<Rect>>,
 }
 impl Anchorer {
-    pub fn new_pos(layout: Layout, start: PositionInPixels2d, pad: f32) -> Self {
+    pub fn new_pos<L: Into<Layout>>(layout: L, start: PositionInPixels2d, pad: f32) -> Self {
         Self::new(layout, Rect::new(start.x, start.y, 0.0, 0.0), pad)
     }
-    pub fn new(layout: Layout, start: Rect, pad: f32) -> Self {
+    pub fn new<L: Into<Layout>>(layout: L, start: Rect, pad: f32) -> Self {
         Self {
-            layout,
+            layout: layout.into(),
             current: start,
             pad,
+            bounds: None,
+            line: None,
+            break_every: None,
+            line_count: 0,
+            first: None,
+            placed: None,
+            cells: Vec::new(),
+            columns: Vec::new(),
         }
     }
+    /// Widgets that would go beyond `bounds` in the layout direction start a new line (or a new
+    /// column, for vertical layouts), `pad` pixels after the previous one. Lines go downwards (or
+    /// rightwards) unless the layout is aligned to the bottom (or right).
+    #[must_use]
+    pub fn with_wrap(mut self, bounds: Rect) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+    /// Lines (or columns, for vertical layouts) have at most `widgets` widgets, e.g. to make a
+    /// form with several columns. Works with or without [`Self::with_wrap`].
+    #[must_use]
+    pub fn with_break_every(mut self, widgets: usize) -> Self {
+        self.break_every = Some(widgets.max(1));
+        self
+    }
+
+    /// Starts a sub-anchorer where the next widget would go, with the perpendicular layout (see
+    /// [`Layout::perpendicular_layout`]) and the same pad. Place widgets with it and then give it
+    /// back to [`Self::join`]. Several forks joined one after the other can have their rows
+    /// aligned with [`Self::align_rows`].
+    pub fn fork(&self) -> Anchorer {
+        let mut fork = Anchorer::new(self.layout.perpendicular_layout(), self.current, self.pad);
+        let next = self.next_anchor(self.pad);
+        let wrapped = self
+            .line_full()
+            .then(|| self.wrapped_anchor(next.get_rect(Vec2::ZERO)));
+        fork.first = Some(wrapped.flatten().unwrap_or(next));
+        fork
+    }
+    /// Continues after everything that `fork` placed, as if it was a single widget.
+    pub fn join(&mut self, fork: Anchorer) {
+        if let Some(placed) = fork.placed {
+            let new_line = self.line_full();
+            self.advance(placed, new_line);
+            self.columns.push(fork.cells);
+        }
+    }
+    /// Aligns the rows of the forks joined in the current line (the columns, for vertical
+    /// layouts), e.g. for a form with a column of labels and a column of controls: the i-th widget
+    /// of each fork goes at the same row, which is as tall as the tallest of them. The forks
+    /// already placed their widgets, so this returns where they go instead, by fork and in the
+    /// order they were placed, to give them to [`Widget::set_rect`].
+    pub fn align_rows(&mut self) -> Vec<Vec<Rect>> {
+        let along_y = matches!(self.layout, Layout::Horizontal { .. });
+        let backwards = match self.layout.perpendicular_layout() {
+            Layout::Vertical { direction, .. } => direction == Vertical::Top,
+            Layout::Horizontal { direction, .. } => direction == Horizontal::Left,
+        };
+        // the start and the end of a rect across the rows
+        let span = |rect: &Rect| {
+            if along_y {
+                (rect.top(), rect.bottom())
+            } else {
+                (rect.left(), rect.right())
+            }
+        };
+        let rows = self.columns.iter().map(Vec::len).max().unwrap_or(0);
+        let firsts = self.columns.iter().filter_map(|cells| cells.first());
+        let mut edge = if backwards {
+            firsts.map(|first| span(first).1).fold(f32::MIN, f32::max)
+        } else {
+            firsts.map(|first| span(first).0).fold(f32::MAX, f32::min)
+        };
+        let mut row_starts = Vec::with_capacity(rows);
+        for row in 0..rows {
+            let cells = self.columns.iter().filter_map(|cells| cells.get(row));
+            let height = cells
+                .map(|cell| span(cell).1 - span(cell).0)
+                .fold(0.0, f32::max);
+            if backwards {
+                row_starts.push(edge - height);
+                edge -= height + self.pad;
+            } else {
+                row_starts.push(edge);
+                edge += height + self.pad;
+            }
+        }
+        for cells in &mut self.columns {
+            for (cell, row_start) in cells.iter_mut().zip(&row_starts) {
+                if along_y {
+                    cell.y = *row_start;
+                } else {
+                    cell.x = *row_start;
+                }
+            }
+        }
+        let bounds = |cells: &[Rect]| cells.iter().copied().reduce(|a, b| a.combine_with(b));
+        if let Some(last) = self.columns.last().and_then(|cells| bounds(cells)) {
+            self.current = last;
+        }
+        if let Some(aligned) = bounds(&self.columns.concat()) {
+            self.line = self.line.map(|line| line.combine_with(aligned));
+            self.placed = self.placed.map(|placed| placed.combine_with(aligned));
+        }
+        self.columns.clone()
+    }
+    /// Bounding rect of all the widgets placed so far, if any.
+    pub fn placed(&self) -> Option<Rect> {
+        self.placed
+    }
+
     pub fn move_and_modify(&mut self, rect: &mut Rect) {
-        let anchor = Anchor::next_to(self.current, self.layout, self.pad);
+        let anchor = self.next_anchor(self.pad);
         *rect = anchor.get_rect(rect.size());
-        self.current = *rect;
+        let wrapped = self.wrapped_anchor(*rect);
+        if let Some(anchor) = wrapped {
+            *rect = anchor.get_rect(rect.size());
+        }
+        self.advance(*rect, wrapped.is_some());
     }
 
     pub fn new_button(&mut self, text: &str, font_size: f32) -> Button {
@@ -43,9 +172,218 @@ impl Anchorer {
         self.new_widget(|anchor| TextRect::new(text, anchor, font_size))
     }
     pub fn new_widget<W: Widget>(&mut self, f: impl Fn(Anchor) -> W) -> W {
-        let anchor = Anchor::next_to(self.current, self.layout, 0.0);
-        let widget = f(anchor);
-        self.current = widget.rect();
+        let anchor = self.next_anchor(0.0);
+        let mut widget = f(anchor);
+        let wrapped = self.wrapped_anchor(widget.rect());
+        if let Some(anchor) = wrapped {
+            widget.reanchor(anchor);
+        }
+        self.advance(widget.rect(), wrapped.is_some());
         widget
     }
+
+    fn next_anchor(&self, pad: f32) -> Anchor {
+        self.first
+            .unwrap_or_else(|| Anchor::next_to(self.current, self.layout, pad))
+    }
+
+    fn line_full(&self) -> bool {
+        self.break_every
+            .is_some_and(|widgets| self.line_count >= widgets)
+    }
+
+    /// Returns where `rect` should go if it doesn't fit in the current line, or the line is full.
+    fn wrapped_anchor(&self, rect: Rect) -> Option<Anchor> {
+        let line = self.line?;
+        let full = self.line_full();
+        let beyond = |exceeds: fn(Rect, Rect) -> bool| {
+            self.bounds.is_some_and(|bounds| exceeds(rect, bounds))
+        };
+        let anchor = match self.layout {
+            Layout::Horizontal {
+                direction,
+                alignment,
+            } => {
+                let (exceeds, start) = if direction == Horizontal::Left {
+                    (beyond(|r, b| r.left() < b.left()), Horizontal::Right)
+                } else {
+                    (beyond(|r, b| r.right() > b.right()), Horizontal::Left)
+                };
+                if !exceeds && !full {
+                    return None;
+                }
+                if alignment == Vertical::Bottom {
+                    Anchor::above(line, start, self.pad)
+                } else {
+                    Anchor::below(line, start, self.pad)
+                }
+            }
+            Layout::Vertical {
+                direction,
+                alignment,
+            } => {
+                let (exceeds, start) = if direction == Vertical::Top {
+                    (beyond(|r, b| r.top() < b.top()), Vertical::Bottom)
+                } else {
+                    (beyond(|r, b| r.bottom() > b.bottom()), Vertical::Top)
+                };
+                if !exceeds && !full {
+                    return None;
+                }
+                if alignment == Horizontal::Right {
+                    Anchor::leftwards(line, start, self.pad)
+                } else {
+                    Anchor::rightwards(line, start, self.pad)
+                }
+            }
+        };
+        Some(anchor)
+    }
+    fn advance(&mut self, rect: Rect, new_line: bool) {
+        self.first = None;
+        self.current = rect;
+        self.cells.push(rect);
+        (self.line, self.line_count) = match self.line {
+            Some(line) if !new_line => (Some(line.combine_with(rect)), self.line_count + 1),
+            _ => {
+                self.columns.clear();
+                (Some(rect), 1)
+            }
+        };
+        self.placed = Some(match self.placed {
+            Some(placed) => placed.combine_with(rect),
+            None => rect,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::anchor::{LayoutV2, Sense, Spot};
+
+    #[test]
+    fn test_wrap() {
+        let layout = Layout::horizontal(Horizontal::Right, Vertical::Top);
+        let start = Rect::new(0.0, 0.0, 0.0, 0.0);
+        let bounds = Rect::new(0.0, 0.0, 100.0, 100.0);
+        let mut anchorer = Anchorer::new(layout, start, 10.0).with_wrap(bounds);
+        let mut rects = [
+            Rect::new(0.0, 0.0, 40.0, 20.0),
+            Rect::new(0.0, 0.0, 40.0, 30.0),
+            Rect::new(0.0, 0.0, 40.0, 20.0),
+            Rect::new(0.0, 0.0, 40.0, 20.0),
+        ];
+        for rect in &mut rects {
+            anchorer.move_and_modify(rect);
+        }
+        assert_eq!(
+            rects,
+            [
+                Rect::new(10.0, 0.0, 40.0, 20.0),
+                Rect::new(60.0, 0.0, 40.0, 30.0),
+                Rect::new(10.0, 40.0, 40.0, 20.0),
+                Rect::new(60.0, 40.0, 40.0, 20.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fork_and_join() {
+        let layout = Layout::horizontal(Horizontal::Right, Vertical::Top);
+        let mut anchorer = Anchorer::new(layout, Rect::new(0.0, 0.0, 0.0, 0.0), 10.0);
+        let mut labels = anchorer.fork();
+        let mut label_rects = [Rect::new(0.0, 0.0, 50.0, 20.0); 2];
+        for rect in &mut label_rects {
+            labels.move_and_modify(rect);
+        }
+        anchorer.join(labels);
+        let mut controls = anchorer.fork();
+        let mut control_rects = [Rect::new(0.0, 0.0, 80.0, 30.0); 2];
+        for rect in &mut control_rects {
+            controls.move_and_modify(rect);
+        }
+        anchorer.join(controls);
+        assert_eq!(
+            label_rects,
+            [
+                Rect::new(10.0, 0.0, 50.0, 20.0),
+                Rect::new(10.0, 30.0, 50.0, 20.0)
+            ]
+        );
+        assert_eq!(
+            control_rects,
+            [
+                Rect::new(70.0, 0.0, 80.0, 30.0),
+                Rect::new(70.0, 40.0, 80.0, 30.0)
+            ]
+        );
+
+        // the second label goes down to the row of the second control
+        let aligned = anchorer.align_rows();
+        assert_eq!(
+            aligned,
+            [
+                [
+                    Rect::new(10.0, 0.0, 50.0, 20.0),
+                    Rect::new(10.0, 40.0, 50.0, 20.0)
+                ],
+                control_rects
+            ]
+        );
+        assert_eq!(anchorer.placed(), Some(Rect::new(10.0, 0.0, 140.0, 70.0)));
+        let mut next = Rect::new(0.0, 0.0, 10.0, 10.0);
+        anchorer.move_and_modify(&mut next);
+        assert_eq!(next, Rect::new(160.0, 0.0, 10.0, 10.0));
+    }
+
+    #[test]
+    fn test_break_every() {
+        let layout = Layout::vertical(Vertical::Bottom, Horizontal::Left);
+        let start = Rect::new(0.0, 0.0, 0.0, 0.0);
+        let mut anchorer = Anchorer::new(layout, start, 10.0).with_break_every(2);
+        let mut rects = [Rect::new(0.0, 0.0, 40.0, 20.0); 3];
+        for rect in &mut rects {
+            anchorer.move_and_modify(rect);
+        }
+        assert_eq!(
+            rects,
+            [
+                Rect::new(0.0, 10.0, 40.0, 20.0),
+                Rect::new(0.0, 40.0, 40.0, 20.0),
+                Rect::new(50.0, 10.0, 40.0, 20.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_layout_v2() {
+        let v2 = LayoutV2::vertical(Sense::Backwards, Spot::End);
+        let layout = Layout::vertical(Vertical::Top, Horizontal::Right);
+        let start = Rect::new(200.0, 200.0, 0.0, 0.0);
+        let bounds = Rect::new(0.0, 0.0, 300.0, 300.0);
+        let place = |mut anchorer: Anchorer| {
+            let mut rects = [Rect::new(0.0, 0.0, 40.0, 60.0); 5];
+            for rect in &mut rects {
+                anchorer.move_and_modify(rect);
+            }
+            rects
+        };
+        let with_v2 = place(Anchorer::new(v2, start, 10.0).with_wrap(bounds));
+        assert_eq!(
+            with_v2,
+            place(Anchorer::new(layout, start, 10.0).with_wrap(bounds))
+        );
+        // upwards from the start, aligned to its right, and wrapping leftwards
+        assert_eq!(
+            with_v2,
+            [
+                Rect::new(160.0, 130.0, 40.0, 60.0),
+                Rect::new(160.0, 60.0, 40.0, 60.0),
+                Rect::new(110.0, 130.0, 40.0, 60.0),
+                Rect::new(110.0, 60.0, 40.0, 60.0),
+                Rect::new(60.0, 130.0, 40.0, 60.0),
+            ]
+        );
+    }
 }
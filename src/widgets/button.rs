@@ -1,11 +1,11 @@
 use crate::draw::{draw_rect, draw_rect_lines};
+use crate::fonts::FontId;
 use crate::input::input_macroquad::InputMacroquad;
 use crate::input::input_trait::InputTrait;
 use crate::widgets::anchor::Anchor;
 use crate::widgets::text::{MeasureText, TextRect};
 use crate::widgets::{interact, Interaction, StateStyle, Style, Widget};
 use macroquad::prelude::{draw_line, Rect};
-use macroquad::text::Font;
 
 pub type RenderButton = fn(interaction: Interaction, text_rect: &TextRect, style: &Style);
 
@@ -31,7 +31,7 @@ impl Button {
         text: &str,
         position_pixels: Anchor,
         font_size: f32,
-        font: Option<&Font>,
+        font: FontId,
         measure_text: MeasureText,
         input: Box<dyn InputTrait>,
     ) -> Self {
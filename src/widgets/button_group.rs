@@ -1,11 +1,14 @@
+use crate::fonts::{FontId, DEFAULT_FONT};
+use crate::i18n;
+use crate::i18n::Localized;
 use crate::input::input_macroquad::InputMacroquad;
 use crate::input::input_trait::InputTrait;
+use crate::measure_cache;
 use crate::widgets::anchor::{Anchor, Horizontal};
 use crate::widgets::button::Button;
-use crate::widgets::text::TextRect;
+use crate::widgets::text::{MeasureText, TextRect};
 use macroquad::math::{vec2, Vec2};
-use macroquad::prelude::{measure_text, Rect};
-use macroquad::text::Font;
+use macroquad::prelude::{measure_text, Rect, TextDimensions};
 use std::mem::ManuallyDrop;
 
 pub struct ButtonGroup {
@@ -15,13 +18,14 @@ pub struct ButtonGroup {
 
 pub struct LabelGroup {
     pub font_size: f32,
-    pub font: Option<Font>,
+    pub font: FontId,
     pub alignment: Horizontal,
     pub direction: Direction,
     pub anchor: Anchor,
     pub pad_x: Option<f32>,
     pub pad_y: Option<f32>,
     pub margin: f32,
+    pub measure_text: MeasureText,
 }
 pub enum Direction {
     Top,
@@ -38,7 +42,7 @@ impl ButtonGroup {
     pub fn new(font_size: f32, anchor: Anchor) -> Self {
         Self::new_with_labels(LabelGroup::new(font_size, anchor))
     }
-    pub fn new_with_font(font_size: f32, font: Option<Font>, anchor: Anchor) -> Self {
+    pub fn new_with_font(font_size: f32, font: FontId, anchor: Anchor) -> Self {
         Self::new_with_labels(LabelGroup::new_with_font(font_size, font, anchor))
     }
     pub fn new_with_labels(label_group: LabelGroup) -> Self {
@@ -70,7 +74,24 @@ impl ButtonGroup {
         unsafe { ManuallyDrop::take(&mut buttons_u.b) }
     }
     pub fn create<S: AsRef<str>, const N: usize>(&self, texts: [S; N]) -> [Button; N] {
-        let text_rects = self.label_group.create(texts);
+        self.buttons_from(self.label_group.create(texts))
+    }
+    pub fn create_localized<const N: usize>(&self, keys: [Localized; N]) -> [Button; N] {
+        self.buttons_from(self.label_group.create_localized(keys))
+    }
+    /// See [`LabelGroup::relocalize`].
+    pub fn relocalize(&self, buttons: &mut [Button]) -> bool {
+        let mut text_rects = buttons
+            .iter()
+            .map(|button| button.text_rect.clone())
+            .collect::<Vec<_>>();
+        let changed = self.label_group.relocalize(&mut text_rects);
+        for (button, text_rect) in buttons.iter_mut().zip(text_rects) {
+            button.text_rect = text_rect;
+        }
+        changed
+    }
+    fn buttons_from<const N: usize>(&self, text_rects: [TextRect; N]) -> [Button; N] {
         let mut buttons = Vec::new();
         for text_rect in text_rects {
             buttons.push(Button::new_from_text_rect_generic(
@@ -88,13 +109,14 @@ impl Default for LabelGroup {
     fn default() -> Self {
         Self {
             font_size: 16.0,
-            font: None,
+            font: DEFAULT_FONT,
             anchor: Anchor::top_left(0.0, 0.0),
             alignment: Horizontal::Center,
             direction: Direction::Bottom,
             pad_x: None,
             pad_y: None,
             margin: 0.0,
+            measure_text,
         }
     }
 }
@@ -106,7 +128,7 @@ impl LabelGroup {
             ..Default::default()
         }
     }
-    pub fn new_with_font(font_size: f32, font: Option<Font>, anchor: Anchor) -> Self {
+    pub fn new_with_font(font_size: f32, font: FontId, anchor: Anchor) -> Self {
         Self {
             font_size,
             font,
@@ -116,7 +138,7 @@ impl LabelGroup {
     }
     pub fn new_generic(
         font_size: f32,
-        font: Option<Font>,
+        font: FontId,
         anchor: Anchor,
         alignment: Horizontal,
         direction: Direction,
@@ -131,11 +153,66 @@ impl LabelGroup {
         }
     }
     pub fn create<S: AsRef<str>, const N: usize>(&self, texts: [S; N]) -> [TextRect; N] {
+        let texts = texts.iter().map(|text| text.as_ref().to_string()).collect();
+        self.create_vec(texts)
+            .try_into()
+            .unwrap_or_else(|v: Vec<_>| {
+                panic!("Expected a Vec of length {} but it was {}", N, v.len())
+            })
+    }
+    pub fn create_localized<const N: usize>(&self, keys: [Localized; N]) -> [TextRect; N] {
+        self.create_localized_vec(keys.into())
+            .try_into()
+            .unwrap_or_else(|v: Vec<_>| {
+                panic!("Expected a Vec of length {} but it was {}", N, v.len())
+            })
+    }
+    pub fn create_localized_vec(&self, keys: Vec<Localized>) -> Vec<TextRect> {
+        let language_revision = i18n::revision();
+        let texts = keys.iter().map(Localized::resolve).collect();
+        let mut text_rects = self.create_vec(texts);
+        for (text_rect, key) in text_rects.iter_mut().zip(keys) {
+            text_rect.localized = Some(key);
+            text_rect.language_revision = language_revision;
+        }
+        text_rects
+    }
+    /// If the language changed, translates the localized texts, and lays out all of them again,
+    /// as they share the size of the longest one. Returns whether anything changed.
+    pub fn relocalize(&self, text_rects: &mut [TextRect]) -> bool {
+        if !text_rects.iter().any(TextRect::needs_relocalize) {
+            return false;
+        }
+        let language_revision = i18n::revision();
+        for text_rect in text_rects.iter_mut() {
+            if let Some(localized) = &text_rect.localized {
+                text_rect.text = localized.resolve();
+            }
+            text_rect.language_revision = language_revision;
+        }
+        self.relayout(text_rects);
+        true
+    }
+    /// Lays out the texts again, e.g. after changing the font size, keeping their localization.
+    pub fn relayout(&self, text_rects: &mut [TextRect]) {
+        let texts = text_rects
+            .iter()
+            .map(|text_rect| text_rect.text.clone())
+            .collect();
+        let new_text_rects = self.create_vec(texts);
+        for (text_rect, mut new_text_rect) in text_rects.iter_mut().zip(new_text_rects) {
+            new_text_rect.localized = text_rect.localized.take();
+            new_text_rect.language_revision = text_rect.language_revision;
+            *text_rect = new_text_rect;
+        }
+    }
+    pub fn create_vec(&self, texts: Vec<String>) -> Vec<TextRect> {
+        let count = texts.len();
         let mut text_rects = Vec::new();
         let mut max_width = 0.0;
         let mut dimensions = Vec::new();
         // font_size doesn't seem to be in pixels across fonts
-        let reference_size = measure_text("Odp", self.font.as_ref(), self.font_size as u16, 1.0);
+        let reference_size = self.measure("Odp");
         // let reference_size1 = measure_text("O", self.font, self.font_size as u16, 1.0);
         // let reference_size2 = measure_text(
         //     "some button to expand",
@@ -160,9 +237,7 @@ impl LabelGroup {
         let min_pad = vec2(pad_x, pad_y);
 
         for text in texts {
-            let text = text.as_ref().to_string();
-            let text_dimensions =
-                measure_text(&text, self.font.as_ref(), self.font_size as u16, 1.0);
+            let text_dimensions = self.measure(&text);
             if text_dimensions.width > max_width {
                 max_width = text_dimensions.width;
             }
@@ -175,8 +250,8 @@ impl LabelGroup {
         let panel_size = -self.margin
             + (elem_size + self.margin)
                 * match self.direction {
-                    Direction::Top | Direction::Bottom => Vec2::new(1.0, N as f32),
-                    Direction::Right | Direction::Left => Vec2::new(N as f32, 1.0),
+                    Direction::Top | Direction::Bottom => Vec2::new(1.0, count as f32),
+                    Direction::Right | Direction::Left => Vec2::new(count as f32, 1.0),
                 };
         let mut top_left = self.anchor.get_top_left_pixel(panel_size);
 
@@ -199,12 +274,14 @@ impl LabelGroup {
                 text,
                 rect,
                 font_size: self.font_size,
-                font: self.font.clone(),
+                font: self.font,
                 pad,
                 offset_y: dimension.offset_y,
                 text_width: dimension.width,
                 text_height: dimension.height,
                 reference_height,
+                localized: None,
+                language_revision: 0,
             };
             text_rects.push(text_rect);
             match self.direction {
@@ -234,9 +311,16 @@ impl LabelGroup {
                 }
             }
         }
-        text_rects.try_into().unwrap_or_else(|v: Vec<_>| {
-            panic!("Expected a Vec of length {} but it was {}", N, v.len())
-        })
+        text_rects
+    }
+    fn measure(&self, text: &str) -> TextDimensions {
+        measure_cache::measure_text(
+            text,
+            self.font,
+            self.font_size as u16,
+            1.0,
+            self.measure_text,
+        )
     }
     /*
     pub fn add(&mut self, text: &str) -> Button {
@@ -284,3 +368,52 @@ impl LabelGroup {
     //     }
     // }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::mock_measure;
+
+    fn label_group() -> LabelGroup {
+        i18n::load_language_str("en", "play = Play\nexit = Exit").unwrap();
+        i18n::load_language_str("es", "play = Jugar partida\nexit = Salir").unwrap();
+        i18n::set_language("en");
+        LabelGroup {
+            font_size: 20.0,
+            measure_text: mock_measure,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_relocalize_labels() {
+        let labels = label_group();
+        let mut text_rects = labels.create_localized_vec(vec!["play".into(), "exit".into()]);
+        assert!(!labels.relocalize(&mut text_rects));
+        // "Play" and "Exit" are 60 pixels wide, plus a pad of 20 at each side
+        assert_eq!(text_rects[1].rect, Rect::new(0.0, 50.0, 100.0, 50.0));
+
+        i18n::set_language("es");
+        assert!(labels.relocalize(&mut text_rects));
+        assert_eq!(text_rects[0].text, "Jugar partida");
+        assert_eq!(text_rects[1].text, "Salir");
+        // both take the width of "Jugar partida"
+        assert_eq!(text_rects[0].rect, Rect::new(0.0, 0.0, 235.0, 50.0));
+        assert_eq!(text_rects[1].rect, Rect::new(0.0, 50.0, 235.0, 50.0));
+        assert!(!labels.relocalize(&mut text_rects));
+    }
+
+    #[test]
+    fn test_relocalize_buttons() {
+        let buttons = ButtonGroup::new_with_labels(label_group());
+        let mut created = buttons.create_localized(["play".into(), "exit".into()]);
+        assert!(!buttons.relocalize(&mut created));
+
+        i18n::set_language("es");
+        assert!(buttons.relocalize(&mut created));
+        assert_eq!(created[0].text_rect.text, "Jugar partida");
+        assert_eq!(created[1].text_rect.text, "Salir");
+        assert_eq!(created[1].rect(), Rect::new(0.0, 50.0, 235.0, 50.0));
+        assert!(!buttons.relocalize(&mut created));
+    }
+}
@@ -0,0 +1,247 @@
+//! Boolean controls: a checkbox or a toggle switch, followed by a label. The whole widget is
+//! clickable, and each click flips the value.
+//!
+//! The drawing of the box or the switch is shared with [`crate::lazy::checkbox`] and
+//! [`crate::elm::checkbox`].
+
+use crate::draw::{draw_rect, draw_rect_lines};
+use crate::fonts::{FontId, DEFAULT_FONT};
+use crate::input::input_macroquad::InputMacroquad;
+use crate::input::input_trait::InputTrait;
+use crate::lazy::responsive::Breakpoint;
+use crate::lazy::Pad;
+use crate::widgets::anchor::Anchor;
+use crate::widgets::text::{MeasureText, Pixels, TextRect};
+use crate::widgets::{interact, Interaction, StateStyle, Style, Widget};
+use crate::SizeInPixels2d;
+use macroquad::math::{vec2, Rect};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ToggleKind {
+    /// a square with a mark inside when checked
+    Checkbox,
+    /// a track twice as wide as high, with a knob on the right when checked
+    Switch,
+}
+
+impl ToggleKind {
+    pub fn indicator_size(self, height: Pixels) -> SizeInPixels2d {
+        match self {
+            ToggleKind::Checkbox => vec2(height, height),
+            ToggleKind::Switch => vec2(height * 2.0, height),
+        }
+    }
+    pub fn name(self) -> &'static str {
+        match self {
+            ToggleKind::Checkbox => "Checkbox",
+            ToggleKind::Switch => "Switch",
+        }
+    }
+}
+
+/// Draws the box or the switch in `rect`.
+pub fn draw_indicator(kind: ToggleKind, rect: Rect, checked: bool, state_style: &StateStyle) {
+    match kind {
+        ToggleKind::Checkbox => {
+            draw_rect(rect, state_style.bg_color);
+            if checked {
+                let mark = Rect::new(
+                    rect.x + rect.w * 0.25,
+                    rect.y + rect.h * 0.25,
+                    rect.w * 0.5,
+                    rect.h * 0.5,
+                );
+                draw_rect(mark, state_style.text_color);
+            }
+        }
+        ToggleKind::Switch => {
+            let track_color = if checked {
+                state_style.border_color
+            } else {
+                state_style.bg_color
+            };
+            draw_rect(rect, track_color);
+            let knob_x = if checked {
+                rect.right() - rect.h
+            } else {
+                rect.x
+            };
+            let knob = Rect::new(knob_x + 3.0, rect.y + 3.0, rect.h - 6.0, rect.h - 6.0);
+            draw_rect(knob, state_style.text_color);
+        }
+    }
+    draw_rect_lines(rect, 2.0, state_style.border_color);
+}
+
+/// The left pad of a widget with the indicator before its label: the indicator with `pad` at
+/// each side, so that it's as far from the label as from the left edge.
+pub fn indicator_pad(kind: ToggleKind, pad: Pixels, height: Pixels) -> Pixels {
+    2.0 * pad + kind.indicator_size(height).x
+}
+
+/// Where the indicator starts in a left pad made by [`indicator_pad`], so that it's centred.
+pub fn indicator_start(kind: ToggleKind, pad_left: Pixels, height: Pixels) -> Pixels {
+    (pad_left - kind.indicator_size(height).x) * 0.5
+}
+
+/// Makes room for the indicator in the left pad of a style, and in the pad of each of its
+/// breakpoints, so that switching variant keeps the room.
+pub fn widen_pads(kind: ToggleKind, pad: &mut Pad, font_size: f32, breakpoints: &mut [Breakpoint]) {
+    pad.left = indicator_pad(kind, pad.left, font_size);
+    for breakpoint in breakpoints {
+        let variant = &mut breakpoint.variant;
+        variant.pad.left = indicator_pad(kind, variant.pad.left, variant.font_size);
+    }
+}
+
+/// Where the indicator of `height` goes in a widget at `rect`, `start` pixels from its left.
+pub fn indicator_rect(kind: ToggleKind, rect: Rect, start: Pixels, height: Pixels) -> Rect {
+    let size = kind.indicator_size(height);
+    Rect::new(
+        rect.x + start,
+        rect.center().y - size.y * 0.5,
+        size.x,
+        size.y,
+    )
+}
+
+pub type RenderCheckbox = fn(checkbox: &Checkbox, style: &Style);
+
+pub struct Checkbox {
+    /// the label, with room on the left for the indicator
+    pub text_rect: TextRect,
+    pub checked: bool,
+    pub kind: ToggleKind,
+    interaction: Interaction,
+    input: Box<dyn InputTrait>,
+}
+
+impl Widget for Checkbox {
+    fn rect(&self) -> Rect {
+        self.text_rect.rect
+    }
+    fn set_rect(&mut self, rect: Rect) {
+        self.text_rect.rect = rect;
+    }
+}
+
+impl Checkbox {
+    pub fn new(text: &str, position_pixels: Anchor, font_size: f32, checked: bool) -> Self {
+        Self::new_generic(
+            text,
+            position_pixels,
+            font_size,
+            checked,
+            ToggleKind::Checkbox,
+            DEFAULT_FONT,
+            macroquad::prelude::measure_text,
+            Box::new(InputMacroquad),
+        )
+    }
+    pub fn new_switch(text: &str, position_pixels: Anchor, font_size: f32, checked: bool) -> Self {
+        Self::new_generic(
+            text,
+            position_pixels,
+            font_size,
+            checked,
+            ToggleKind::Switch,
+            DEFAULT_FONT,
+            macroquad::prelude::measure_text,
+            Box::new(InputMacroquad),
+        )
+    }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_generic(
+        text: &str,
+        position_pixels: Anchor,
+        font_size: f32,
+        checked: bool,
+        kind: ToggleKind,
+        font: FontId,
+        measure_text: MeasureText,
+        input: Box<dyn InputTrait>,
+    ) -> Self {
+        let mut text_rect =
+            TextRect::new_generic(text, Anchor::default(), font_size, font, measure_text);
+        let pad = text_rect.pad.x;
+        text_rect.pad.x = indicator_pad(kind, pad, text_rect.reference_height);
+        text_rect.rect.w += text_rect.pad.x - pad;
+        let mut checkbox = Self {
+            text_rect,
+            checked,
+            kind,
+            interaction: Interaction::None,
+            input,
+        };
+        checkbox.reanchor(position_pixels);
+        checkbox
+    }
+
+    /// Flips the value if clicked.
+    pub fn interact(&mut self) -> Interaction {
+        self.interaction = interact(self.rect(), &self.input);
+        if self.interaction.is_clicked() {
+            self.checked = !self.checked;
+        }
+        self.interaction
+    }
+    pub fn interaction(&self) -> Interaction {
+        self.interaction
+    }
+    pub fn indicator_rect(&self) -> Rect {
+        let text_rect = &self.text_rect;
+        let height = text_rect.reference_height;
+        let start = indicator_start(self.kind, text_rect.pad.x, height);
+        indicator_rect(self.kind, self.rect(), start, height)
+    }
+    pub fn render_default(&self, style: &Style) {
+        self.render(style, render_checkbox);
+    }
+    pub fn render(&self, style: &Style, render_checkbox: RenderCheckbox) {
+        render_checkbox(self, style);
+    }
+}
+
+pub fn render_checkbox(checkbox: &Checkbox, style: &Style) {
+    let state_style = style.choose(checkbox.interaction);
+    draw_indicator(
+        checkbox.kind,
+        checkbox.indicator_rect(),
+        checkbox.checked,
+        state_style,
+    );
+    checkbox.text_rect.render_text(style.at_rest.text_color);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{mock_measure, MockInput};
+
+    #[test]
+    fn test_checkbox() {
+        let input = MockInput::default();
+        let mut checkbox = Checkbox::new_generic(
+            "label",
+            Anchor::top_left(0.0, 0.0),
+            16.0,
+            false,
+            ToggleKind::Checkbox,
+            DEFAULT_FONT,
+            mock_measure,
+            Box::new(Clone::clone(&input)),
+        );
+        // the label is 60 wide and the pad 16, so the indicator is 16 from the left and the label
+        assert_eq!(checkbox.rect(), Rect::new(0.0, 0.0, 124.0, 40.0));
+        assert_eq!(checkbox.indicator_rect(), Rect::new(16.0, 12.0, 16.0, 16.0));
+        assert_eq!(checkbox.text_rect.pad.x, 48.0);
+
+        assert!(!checkbox.interact().is_clicked());
+        input.click(vec2(100.0, 20.0));
+        assert!(checkbox.interact().is_clicked());
+        assert!(checkbox.checked);
+        input.clear();
+        assert!(!checkbox.interact().is_clicked());
+        assert!(checkbox.checked);
+    }
+}
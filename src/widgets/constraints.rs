@@ -0,0 +1,413 @@
+//! Layout by relations between widgets, as an alternative to chaining [`Anchor`]s by hand.
+//!
+//! Each [`Constraint`] says that an attribute of a widget equals an attribute of another widget
+//! (or of the screen) times a multiplier plus an offset, like "the left edge of the score is 8
+//! pixels to the right of the health bar" or "the minimap is half as wide as the screen". The
+//! [`ConstraintSolver`] resolves them in whatever order they depend on each other, and solves
+//! again only after the screen or the size of a widget changes.
+//!
+//! Each axis of a widget can have up to 2 constraints: a length and an edge, or 2 edges. Missing
+//! ones are taken from the natural size of the widget and the top left corner of the screen.
+//!
+//! [`Anchor`]: crate::widgets::anchor::Anchor
+
+use crate::widgets::text::Pixels;
+use crate::widgets::Widget;
+use crate::SizeInPixels2d;
+use macroquad::math::Rect;
+use std::fmt::{Display, Formatter};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Attribute {
+    Left,
+    Right,
+    Top,
+    Bottom,
+    CenterX,
+    CenterY,
+    Width,
+    Height,
+}
+
+impl Attribute {
+    /// 0 for the horizontal attributes, 1 for the vertical ones.
+    pub fn axis(&self) -> usize {
+        match self {
+            Attribute::Left | Attribute::Right | Attribute::CenterX | Attribute::Width => 0,
+            Attribute::Top | Attribute::Bottom | Attribute::CenterY | Attribute::Height => 1,
+        }
+    }
+
+    /// How far along the length this edge is, or None for the lengths.
+    fn edge_fraction(&self) -> Option<f32> {
+        match self {
+            Attribute::Left | Attribute::Top => Some(0.0),
+            Attribute::CenterX | Attribute::CenterY => Some(0.5),
+            Attribute::Right | Attribute::Bottom => Some(1.0),
+            Attribute::Width | Attribute::Height => None,
+        }
+    }
+
+    fn value(&self, start: Pixels, length: Pixels) -> Pixels {
+        match self.edge_fraction() {
+            Some(fraction) => start + fraction * length,
+            None => length,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct WidgetId(usize);
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Target {
+    Screen,
+    Widget(WidgetId),
+}
+
+impl From<WidgetId> for Target {
+    fn from(widget: WidgetId) -> Self {
+        Target::Widget(widget)
+    }
+}
+
+/// `widget.attribute = target.target_attribute * multiplier + offset`
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Constraint {
+    pub widget: WidgetId,
+    pub attribute: Attribute,
+    pub target: Target,
+    pub target_attribute: Attribute,
+    pub multiplier: f32,
+    pub offset: Pixels,
+}
+
+impl Constraint {
+    pub fn new<T: Into<Target>>(
+        widget: WidgetId,
+        attribute: Attribute,
+        target: T,
+        target_attribute: Attribute,
+    ) -> Self {
+        Self {
+            widget,
+            attribute,
+            target: target.into(),
+            target_attribute,
+            multiplier: 1.0,
+            offset: 0.0,
+        }
+    }
+    pub fn plus(self, offset: Pixels) -> Self {
+        Self { offset, ..self }
+    }
+    pub fn times(self, multiplier: f32) -> Self {
+        Self { multiplier, ..self }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ConstraintError {
+    /// The widgets that depend on themselves, directly or through other widgets. Widgets that
+    /// only depend on a cycle are not included.
+    Cycle(Vec<WidgetId>),
+    /// More than 2 constraints in an axis, 2 lengths, or 2 edges that can't give a length, like
+    /// `Left` twice.
+    Overconstrained { widget: WidgetId, axis: usize },
+}
+
+impl Display for ConstraintError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConstraintError::Cycle(widgets) => {
+                write!(
+                    f,
+                    "constraints of widgets {:?} depend on themselves",
+                    widgets
+                )
+            }
+            ConstraintError::Overconstrained { widget, axis } => {
+                write!(f, "too many constraints for {:?} in axis {}", widget, axis)
+            }
+        }
+    }
+}
+
+pub struct ConstraintSolver {
+    natural_sizes: Vec<SizeInPixels2d>,
+    rects: Vec<Rect>,
+    constraints: Vec<Constraint>,
+    screen: Rect,
+    dirty: bool,
+}
+
+/// Start and length of a widget in one axis.
+type Span = (Pixels, Pixels);
+
+/// A widget and one of its axes.
+type Node = (usize, usize);
+
+/// The widgets with an axis in `pending` that depends on itself through other `pending` axes.
+fn in_cycles(pending: &[Node], by_axis: &[[Vec<Constraint>; 2]]) -> Vec<WidgetId> {
+    let dependencies = |(widget, axis): Node| {
+        by_axis[widget][axis]
+            .iter()
+            .filter_map(|constraint| match constraint.target {
+                Target::Screen => None,
+                Target::Widget(target) => Some((target.0, constraint.target_attribute.axis())),
+            })
+            .filter(|node| pending.contains(node))
+            .collect::<Vec<_>>()
+    };
+    let reaches_itself = |start: Node| {
+        let mut visited = Vec::new();
+        let mut next = dependencies(start);
+        while let Some(node) = next.pop() {
+            if node == start {
+                return true;
+            }
+            if !visited.contains(&node) {
+                visited.push(node);
+                next.extend(dependencies(node));
+            }
+        }
+        false
+    };
+    let mut widgets = pending
+        .iter()
+        .filter(|node| reaches_itself(**node))
+        .map(|(widget, _)| WidgetId(*widget))
+        .collect::<Vec<_>>();
+    widgets.dedup();
+    widgets
+}
+
+impl ConstraintSolver {
+    pub fn new(screen: Rect) -> Self {
+        Self {
+            natural_sizes: Vec::new(),
+            rects: Vec::new(),
+            constraints: Vec::new(),
+            screen,
+            dirty: true,
+        }
+    }
+
+    /// `natural_size` is used in the axes where the constraints don't give a length.
+    pub fn add_widget(&mut self, natural_size: SizeInPixels2d) -> WidgetId {
+        self.natural_sizes.push(natural_size);
+        self.rects.push(Rect::new(
+            self.screen.x,
+            self.screen.y,
+            natural_size.x,
+            natural_size.y,
+        ));
+        self.dirty = true;
+        WidgetId(self.rects.len() - 1)
+    }
+    pub fn add(&mut self, constraint: Constraint) {
+        self.constraints.push(constraint);
+        self.dirty = true;
+    }
+    pub fn set_natural_size(&mut self, widget: WidgetId, natural_size: SizeInPixels2d) {
+        if self.natural_sizes[widget.0] != natural_size {
+            self.natural_sizes[widget.0] = natural_size;
+            self.dirty = true;
+        }
+    }
+    pub fn set_screen(&mut self, screen: Rect) {
+        if self.screen != screen {
+            self.screen = screen;
+            self.dirty = true;
+        }
+    }
+
+    /// As computed by the last [`Self::solve`].
+    pub fn rect(&self, widget: WidgetId) -> Rect {
+        self.rects[widget.0]
+    }
+    pub fn apply<W: Widget>(&self, widget_id: WidgetId, widget: &mut W) {
+        widget.set_rect(self.rect(widget_id));
+    }
+
+    /// Resolves all the constraints if anything changed since the last call. Returns whether it
+    /// had to solve. After an error the rects keep their previous values.
+    pub fn solve(&mut self) -> Result<bool, ConstraintError> {
+        if !self.dirty {
+            return Ok(false);
+        }
+        let mut by_axis = vec![[Vec::new(), Vec::new()]; self.rects.len()];
+        for constraint in &self.constraints {
+            by_axis[constraint.widget.0][constraint.attribute.axis()].push(*constraint);
+        }
+        for (i, axes) in by_axis.iter().enumerate() {
+            for (axis, constraints) in axes.iter().enumerate() {
+                check_count(constraints, WidgetId(i), axis)?;
+            }
+        }
+        let mut spans: Vec<[Option<Span>; 2]> = vec![[None, None]; self.rects.len()];
+        loop {
+            let mut progress = false;
+            let mut pending = Vec::new();
+            for (i, axes) in by_axis.iter().enumerate() {
+                for (axis, constraints) in axes.iter().enumerate() {
+                    if spans[i][axis].is_some() {
+                        continue;
+                    }
+                    match self.solve_axis(constraints, &spans, i, axis) {
+                        Some(span) => {
+                            spans[i][axis] = Some(span);
+                            progress = true;
+                        }
+                        None => pending.push((i, axis)),
+                    }
+                }
+            }
+            if pending.is_empty() {
+                break;
+            }
+            if !progress {
+                return Err(ConstraintError::Cycle(in_cycles(&pending, &by_axis)));
+            }
+        }
+        for (rect, span) in self.rects.iter_mut().zip(spans) {
+            let [(x, w), (y, h)] = span.map(|span| span.unwrap());
+            *rect = Rect::new(x, y, w, h);
+        }
+        self.dirty = false;
+        Ok(true)
+    }
+
+    /// None if some constraint targets a widget that is not solved yet in the needed axis.
+    fn solve_axis(
+        &self,
+        constraints: &[Constraint],
+        spans: &[[Option<Span>; 2]],
+        widget: usize,
+        axis: usize,
+    ) -> Option<Span> {
+        let mut length = None;
+        let mut edges = Vec::new();
+        for constraint in constraints {
+            let target_axis = constraint.target_attribute.axis();
+            let (start, target_length) = match constraint.target {
+                Target::Screen => screen_span(self.screen, target_axis),
+                Target::Widget(target) => spans[target.0][target_axis]?,
+            };
+            let value = constraint.target_attribute.value(start, target_length)
+                * constraint.multiplier
+                + constraint.offset;
+            match constraint.attribute.edge_fraction() {
+                Some(fraction) => edges.push((fraction, value)),
+                None => length = Some(value.max(0.0)),
+            }
+        }
+        let screen_start = screen_span(self.screen, axis).0;
+        let natural = self.natural_sizes[widget][axis];
+        let span = match (length, edges.as_slice()) {
+            (length, []) => (screen_start, length.unwrap_or(natural)),
+            (length, [(fraction, value)]) => {
+                let length = length.unwrap_or(natural);
+                (value - fraction * length, length)
+            }
+            (_, [(fraction_1, value_1), (fraction_2, value_2), ..]) => {
+                let length = ((value_2 - value_1) / (fraction_2 - fraction_1)).max(0.0);
+                (value_1 - fraction_1 * length, length)
+            }
+        };
+        Some(span)
+    }
+}
+
+fn screen_span(screen: Rect, axis: usize) -> Span {
+    if axis == 0 {
+        (screen.x, screen.w)
+    } else {
+        (screen.y, screen.h)
+    }
+}
+
+fn check_count(
+    constraints: &[Constraint],
+    widget: WidgetId,
+    axis: usize,
+) -> Result<(), ConstraintError> {
+    let fractions = constraints
+        .iter()
+        .filter_map(|constraint| constraint.attribute.edge_fraction())
+        .collect::<Vec<_>>();
+    let lengths = constraints.len() - fractions.len();
+    let same_edge = fractions.len() == 2 && fractions[0] == fractions[1];
+    if constraints.len() > 2 || lengths > 1 || same_edge {
+        Err(ConstraintError::Overconstrained { widget, axis })
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use macroquad::math::vec2;
+    use Attribute::*;
+
+    #[test]
+    fn test_solve_hud() {
+        let mut solver = ConstraintSolver::new(Rect::new(0.0, 0.0, 800.0, 600.0));
+        let health = solver.add_widget(vec2(100.0, 40.0));
+        let score = solver.add_widget(vec2(60.0, 20.0));
+        let minimap = solver.add_widget(vec2(10.0, 10.0));
+        // added out of order on purpose
+        solver.add(Constraint::new(score, Left, health, Right).plus(8.0));
+        solver.add(Constraint::new(score, CenterY, health, CenterY));
+        solver.add(Constraint::new(health, Left, Target::Screen, Left).plus(10.0));
+        solver.add(Constraint::new(health, Top, Target::Screen, Top).plus(10.0));
+        solver.add(Constraint::new(minimap, Width, Target::Screen, Width).times(0.5));
+        solver.add(Constraint::new(minimap, Height, minimap, Width));
+        solver.add(Constraint::new(minimap, Right, Target::Screen, Right).plus(-10.0));
+        solver.add(Constraint::new(minimap, Bottom, Target::Screen, Bottom).plus(-10.0));
+
+        assert_eq!(solver.solve(), Ok(true));
+        assert_eq!(solver.rect(health), Rect::new(10.0, 10.0, 100.0, 40.0));
+        assert_eq!(solver.rect(score), Rect::new(118.0, 20.0, 60.0, 20.0));
+        assert_eq!(solver.rect(minimap), Rect::new(390.0, 190.0, 400.0, 400.0));
+        assert_eq!(solver.solve(), Ok(false));
+
+        solver.set_screen(Rect::new(0.0, 0.0, 400.0, 300.0));
+        solver.set_natural_size(health, vec2(50.0, 40.0));
+        assert_eq!(solver.solve(), Ok(true));
+        assert_eq!(solver.rect(score), Rect::new(68.0, 20.0, 60.0, 20.0));
+        assert_eq!(solver.rect(minimap), Rect::new(190.0, 90.0, 200.0, 200.0));
+    }
+
+    #[test]
+    fn test_two_edges_give_the_length() {
+        let mut solver = ConstraintSolver::new(Rect::new(0.0, 0.0, 800.0, 600.0));
+        let bar = solver.add_widget(vec2(10.0, 10.0));
+        solver.add(Constraint::new(bar, Left, Target::Screen, Left).plus(20.0));
+        solver.add(Constraint::new(bar, Right, Target::Screen, CenterX));
+        solver.solve().unwrap();
+        assert_eq!(solver.rect(bar), Rect::new(20.0, 0.0, 380.0, 10.0));
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut solver = ConstraintSolver::new(Rect::new(0.0, 0.0, 800.0, 600.0));
+        let a = solver.add_widget(vec2(10.0, 10.0));
+        let b = solver.add_widget(vec2(10.0, 10.0));
+        let c = solver.add_widget(vec2(10.0, 10.0));
+        solver.add(Constraint::new(a, Left, b, Right));
+        solver.add(Constraint::new(b, Left, a, Right));
+        solver.add(Constraint::new(c, Top, a, Bottom));
+        // c can't be solved either, but only because it depends on the cycle
+        solver.add(Constraint::new(c, Left, b, Left));
+        assert_eq!(solver.solve(), Err(ConstraintError::Cycle(vec![a, b])));
+
+        let mut solver = ConstraintSolver::new(Rect::new(0.0, 0.0, 800.0, 600.0));
+        let a = solver.add_widget(vec2(10.0, 10.0));
+        solver.add(Constraint::new(a, Top, Target::Screen, Top));
+        solver.add(Constraint::new(a, Top, Target::Screen, CenterY));
+        let expected = ConstraintError::Overconstrained { widget: a, axis: 1 };
+        assert_eq!(solver.solve(), Err(expected));
+    }
+}
@@ -0,0 +1,200 @@
+//! Docking of HUD widgets to the corners and edges of the screen, inside a safe area (e.g. to
+//! avoid a notch or the rounded corners of a phone).
+//!
+//! Widgets docked at the same place are stacked with an [`Anchorer`] in the order they were
+//! added, away from the edge: downwards from the top, upwards from the bottom, and inwards from
+//! the middle of the left and right edges. The rects are computed again only after the screen,
+//! the safe area or the size of a widget changes.
+
+use crate::lazy::{add_contour, Pad};
+use crate::widgets::anchor::{Horizontal, Layout, Vertical};
+use crate::widgets::anchorer::Anchorer;
+use crate::widgets::text::Pixels;
+use crate::widgets::Widget;
+use crate::SizeInPixels2d;
+use macroquad::math::{vec2, Rect};
+
+/// A corner, the middle of an edge, or the center of the screen.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Dock {
+    pub horizontal: Horizontal,
+    pub vertical: Vertical,
+}
+
+impl Dock {
+    pub const TOP_LEFT: Dock = Dock::new(Horizontal::Left, Vertical::Top);
+    pub const TOP: Dock = Dock::new(Horizontal::Center, Vertical::Top);
+    pub const TOP_RIGHT: Dock = Dock::new(Horizontal::Right, Vertical::Top);
+    pub const LEFT: Dock = Dock::new(Horizontal::Left, Vertical::Center);
+    pub const CENTER: Dock = Dock::new(Horizontal::Center, Vertical::Center);
+    pub const RIGHT: Dock = Dock::new(Horizontal::Right, Vertical::Center);
+    pub const BOTTOM_LEFT: Dock = Dock::new(Horizontal::Left, Vertical::Bottom);
+    pub const BOTTOM: Dock = Dock::new(Horizontal::Center, Vertical::Bottom);
+    pub const BOTTOM_RIGHT: Dock = Dock::new(Horizontal::Right, Vertical::Bottom);
+
+    pub const fn new(horizontal: Horizontal, vertical: Vertical) -> Self {
+        Self {
+            horizontal,
+            vertical,
+        }
+    }
+
+    /// How the widgets docked here are stacked.
+    pub fn layout(&self) -> Layout {
+        match (self.horizontal, self.vertical) {
+            (horizontal, Vertical::Top) => Layout::vertical(Vertical::Bottom, horizontal),
+            (horizontal, Vertical::Bottom) => Layout::vertical(Vertical::Top, horizontal),
+            (Horizontal::Left, Vertical::Center) => {
+                Layout::horizontal(Horizontal::Right, Vertical::Center)
+            }
+            (Horizontal::Right, Vertical::Center) => {
+                Layout::horizontal(Horizontal::Left, Vertical::Center)
+            }
+            (Horizontal::Center, Vertical::Center) => {
+                Layout::vertical(Vertical::Bottom, Horizontal::Center)
+            }
+        }
+    }
+
+    /// Zero sized rect `gap` pixels before the spot of `area`, so that an [`Anchorer`] places the
+    /// first widget right at the spot.
+    fn start(&self, area: Rect, gap: Pixels) -> Rect {
+        let x = self.horizontal.x_pad(area, vec2(0.0, 0.0));
+        let y = self.vertical.y_pad(area, vec2(0.0, 0.0));
+        let back = match self.layout() {
+            Layout::Horizontal { direction, .. } => vec2(-sign(direction.fraction()) * gap, 0.0),
+            Layout::Vertical { direction, .. } => vec2(0.0, -sign(direction.fraction()) * gap),
+        };
+        Rect::new(x + back.x, y + back.y, 0.0, 0.0)
+    }
+}
+
+/// 1.0 for Right or Bottom, -1.0 for Left or Top.
+fn sign(fraction: f32) -> f32 {
+    fraction * 2.0 - 1.0
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct DockedId(usize);
+
+struct Docked {
+    dock: Dock,
+    size: SizeInPixels2d,
+    rect: Rect,
+}
+
+pub struct Hud {
+    screen: Rect,
+    safe_area: Pad,
+    gap: Pixels,
+    docked: Vec<Docked>,
+    dirty: bool,
+}
+
+impl Hud {
+    /// `safe_area` are the insets from the screen borders, and `gap` the pixels between widgets
+    /// docked at the same place.
+    pub fn new<P: Into<Pad>>(screen: Rect, safe_area: P, gap: Pixels) -> Self {
+        Self {
+            screen,
+            safe_area: safe_area.into(),
+            gap,
+            docked: Vec::new(),
+            dirty: true,
+        }
+    }
+
+    /// Docks a widget of size `size` after the ones already at `dock`.
+    pub fn add(&mut self, dock: Dock, size: SizeInPixels2d) -> DockedId {
+        self.docked.push(Docked {
+            dock,
+            size,
+            rect: Rect::new(self.screen.x, self.screen.y, size.x, size.y),
+        });
+        self.dirty = true;
+        DockedId(self.docked.len() - 1)
+    }
+    pub fn set_size(&mut self, docked: DockedId, size: SizeInPixels2d) {
+        if self.docked[docked.0].size != size {
+            self.docked[docked.0].size = size;
+            self.dirty = true;
+        }
+    }
+    pub fn set_screen(&mut self, screen: Rect) {
+        if self.screen != screen {
+            self.screen = screen;
+            self.dirty = true;
+        }
+    }
+    pub fn set_safe_area<P: Into<Pad>>(&mut self, safe_area: P) {
+        let safe_area = safe_area.into();
+        if self.safe_area != safe_area {
+            self.safe_area = safe_area;
+            self.dirty = true;
+        }
+    }
+
+    /// As computed by the last [`Self::relayout`].
+    pub fn rect(&self, docked: DockedId) -> Rect {
+        self.docked[docked.0].rect
+    }
+    pub fn apply<W: Widget>(&self, docked: DockedId, widget: &mut W) {
+        widget.set_rect(self.rect(docked));
+    }
+
+    /// Computes the rects of all the docked widgets if anything changed since the last call.
+    /// Returns whether it had to.
+    pub fn relayout(&mut self) -> bool {
+        if !self.dirty {
+            return false;
+        }
+        let area = add_contour(self.screen, -self.safe_area);
+        let mut anchorers: Vec<(Dock, Anchorer)> = Vec::new();
+        for docked in &mut self.docked {
+            let index = match anchorers.iter().position(|(dock, _)| *dock == docked.dock) {
+                Some(index) => index,
+                None => {
+                    let start = docked.dock.start(area, self.gap);
+                    let anchorer = Anchorer::new(docked.dock.layout(), start, self.gap);
+                    anchorers.push((docked.dock, anchorer));
+                    anchorers.len() - 1
+                }
+            };
+            let mut rect = Rect::new(0.0, 0.0, docked.size.x, docked.size.y);
+            anchorers[index].1.move_and_modify(&mut rect);
+            docked.rect = rect;
+        }
+        self.dirty = false;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stacking() {
+        let screen = Rect::new(0.0, 0.0, 800.0, 600.0);
+        let mut hud = Hud::new(screen, Pad::new_symmetric(16.0), 4.0);
+        let score = hud.add(Dock::TOP_RIGHT, vec2(100.0, 20.0));
+        let lives = hud.add(Dock::TOP_RIGHT, vec2(60.0, 20.0));
+        let minimap = hud.add(Dock::BOTTOM_LEFT, vec2(50.0, 50.0));
+        let hint = hud.add(Dock::BOTTOM_LEFT, vec2(80.0, 10.0));
+        let tools = hud.add(Dock::LEFT, vec2(30.0, 30.0));
+        let more_tools = hud.add(Dock::LEFT, vec2(30.0, 30.0));
+        assert!(hud.relayout());
+        assert!(!hud.relayout());
+        assert_eq!(hud.rect(score), Rect::new(684.0, 16.0, 100.0, 20.0));
+        assert_eq!(hud.rect(lives), Rect::new(724.0, 40.0, 60.0, 20.0));
+        assert_eq!(hud.rect(minimap), Rect::new(16.0, 534.0, 50.0, 50.0));
+        assert_eq!(hud.rect(hint), Rect::new(16.0, 520.0, 80.0, 10.0));
+        assert_eq!(hud.rect(tools), Rect::new(16.0, 285.0, 30.0, 30.0));
+        assert_eq!(hud.rect(more_tools), Rect::new(50.0, 285.0, 30.0, 30.0));
+
+        hud.set_screen(Rect::new(0.0, 0.0, 1000.0, 600.0));
+        assert!(hud.relayout());
+        assert_eq!(hud.rect(score), Rect::new(884.0, 16.0, 100.0, 20.0));
+        assert_eq!(hud.rect(minimap), Rect::new(16.0, 534.0, 50.0, 50.0));
+    }
+}
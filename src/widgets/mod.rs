@@ -1,14 +1,20 @@
+use crate::clip;
 use crate::input::input_trait::InputTrait;
 use crate::widgets::anchor::Anchor;
 use crate::{PositionInPixels2d, SizeInPixels2d};
 use macroquad::color::BLACK;
 use macroquad::color_u8;
-use macroquad::prelude::{Color, MouseButton, Rect};
+use macroquad::prelude::{Color, MouseButton, Rect, Vec2};
 
 pub mod anchor;
 pub mod anchorer;
 pub mod button;
 pub mod button_group;
+pub mod checkbox;
+pub mod constraints;
+pub mod hud;
+pub mod radio_group;
+pub mod rect_cut;
 pub mod text;
 pub mod texture_button;
 
@@ -68,6 +74,22 @@ pub struct StateColor {
     pub bg_color: Color,
     pub text_color: Color,
     pub border_color: Color,
+    pub text_outline: Option<TextOutline>,
+    pub text_shadow: Option<TextShadow>,
+}
+
+/// Makes text readable over busy backgrounds.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TextOutline {
+    pub thickness: f32,
+    pub color: Color,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TextShadow {
+    /// from the text, positive x goes right and positive y goes down
+    pub offset: Vec2,
+    pub color: Color,
 }
 
 pub type Coloring = Style;
@@ -86,16 +108,22 @@ impl Style {
                 bg_color: LIGHT_BLUE_BACKGROUND,
                 text_color: BLACK_BLUE_BACKGROUND,
                 border_color: DARK_BLUE_BACKGROUND,
+                text_outline: None,
+                text_shadow: None,
             },
             hovered: StateStyle {
                 bg_color: MID_BLUE_BACKGROUND,
                 text_color: WHITE_BLUE_BACKGROUND,
                 border_color: LIGHT_BLUE_BACKGROUND,
+                text_outline: None,
+                text_shadow: None,
             },
             pressed: StateStyle {
                 bg_color: BLACK_BLUE_BACKGROUND,
                 text_color: WHITE_BLUE_BACKGROUND,
                 border_color: LIGHT_BLUE_BACKGROUND,
+                text_outline: None,
+                text_shadow: None,
             },
         }
     }
@@ -139,8 +167,9 @@ impl Interaction {
     }
 }
 
+/// Only the part of `rect` inside the current [`clip`] can be hovered.
 pub fn interact(rect: Rect, input: &Box<dyn InputTrait>) -> Interaction {
-    if rect.contains(input.mouse_position()) {
+    if clip::clip_rect(rect).contains(input.mouse_position()) {
         if input.is_mouse_button_down(MouseButton::Left) {
             Interaction::Pressing
         } else if input.is_mouse_button_released(MouseButton::Left) {
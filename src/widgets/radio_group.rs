@@ -0,0 +1,174 @@
+//! A radio group or segmented control: several options of the same size, laid out by a
+//! [`LabelGroup`], of which exactly one is selected. Clicking an option selects it, and if
+//! enabled, the arrow keys move the selection along the group, wrapping around.
+//!
+//! See [`crate::elm::radio_group`] for the elm version.
+
+use crate::input::input_macroquad::InputMacroquad;
+use crate::input::input_trait::InputTrait;
+use crate::widgets::button::{render_button, Button};
+use crate::widgets::button_group::{Direction, LabelGroup};
+use crate::widgets::{StateStyle, Style};
+use macroquad::input::KeyCode;
+
+pub struct RadioGroup {
+    pub buttons: Vec<Button>,
+    selected: usize,
+    /// if true, the arrow keys change the selection. Off by default, as every radio group on
+    /// screen would react to the same keys
+    pub keyboard: bool,
+    next_key: KeyCode,
+    previous_key: KeyCode,
+    input: Box<dyn InputTrait>,
+}
+
+impl RadioGroup {
+    /// `selected` is clamped to the available options.
+    pub fn new<S: AsRef<str>>(label_group: &LabelGroup, texts: &[S], selected: usize) -> Self {
+        Self::new_generic(label_group, texts, selected, Box::new(InputMacroquad))
+    }
+    pub fn new_generic<S: AsRef<str>>(
+        label_group: &LabelGroup,
+        texts: &[S],
+        selected: usize,
+        input: Box<dyn InputTrait>,
+    ) -> Self {
+        assert!(!texts.is_empty(), "a radio group needs at least one option");
+        let texts = texts.iter().map(|text| text.as_ref().to_string()).collect();
+        let buttons = label_group
+            .create_vec(texts)
+            .into_iter()
+            .map(|text_rect| Button::new_from_text_rect_generic(text_rect, input.clone()))
+            .collect::<Vec<_>>();
+        let (next_key, previous_key) = match label_group.direction {
+            Direction::Bottom => (KeyCode::Down, KeyCode::Up),
+            Direction::Top => (KeyCode::Up, KeyCode::Down),
+            Direction::Right => (KeyCode::Right, KeyCode::Left),
+            Direction::Left => (KeyCode::Left, KeyCode::Right),
+        };
+        Self {
+            selected: selected.min(buttons.len() - 1),
+            buttons,
+            keyboard: false,
+            next_key,
+            previous_key,
+            input,
+        }
+    }
+
+    /// Returns the newly selected option, if the selection changed.
+    pub fn interact(&mut self) -> Option<usize> {
+        let mut selected = self.selected;
+        for (i, button) in self.buttons.iter_mut().enumerate() {
+            if button.interact().is_clicked() {
+                selected = i;
+            }
+        }
+        if self.keyboard {
+            selected = select_with_keys(
+                self.input.as_ref(),
+                selected,
+                self.buttons.len(),
+                self.next_key,
+                self.previous_key,
+            );
+        }
+        self.select(selected).then_some(selected)
+    }
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+    /// Returns whether the selection changed. Out of range options are ignored.
+    pub fn select(&mut self, option: usize) -> bool {
+        let changed = option != self.selected && option < self.buttons.len();
+        if changed {
+            self.selected = option;
+        }
+        changed
+    }
+
+    /// Draws the selected option with the pressed style of `style`.
+    pub fn render_default(&self, style: &Style) {
+        self.render(style, &style.pressed);
+    }
+    pub fn render(&self, style: &Style, selected_style: &StateStyle) {
+        for (i, button) in self.buttons.iter().enumerate() {
+            if i == self.selected {
+                render_button(
+                    button.interaction(),
+                    &button.text_rect,
+                    &uniform(selected_style),
+                );
+            } else {
+                button.render_default(style);
+            }
+        }
+    }
+}
+
+/// A style that looks the same in every interaction.
+fn uniform(state_style: &StateStyle) -> Style {
+    Style {
+        at_rest: *state_style,
+        hovered: *state_style,
+        pressed: *state_style,
+    }
+}
+
+/// The selection after pressing `next` or `previous`, wrapping around the `count` options.
+pub fn select_with_keys(
+    input: &dyn InputTrait,
+    selected: usize,
+    count: usize,
+    next: KeyCode,
+    previous: KeyCode,
+) -> usize {
+    if input.is_key_pressed(next) {
+        (selected + 1) % count
+    } else if input.is_key_pressed(previous) {
+        (selected + count - 1) % count
+    } else {
+        selected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{mock_measure, MockInput};
+    use crate::widgets::anchor::Anchor;
+
+    #[test]
+    fn test_radio_group() {
+        let input = MockInput::default();
+        let label_group = LabelGroup {
+            font_size: 16.0,
+            anchor: Anchor::top_left(0.0, 0.0),
+            direction: Direction::Right,
+            measure_text: mock_measure,
+            ..Default::default()
+        };
+        let texts = ["easy", "normal", "hard"];
+        let mut group =
+            RadioGroup::new_generic(&label_group, &texts, 7, Box::new(Clone::clone(&input)));
+        assert_eq!(group.selected(), 2);
+
+        input.click(group.buttons[0].rect().center());
+        assert_eq!(group.interact(), Some(0));
+        assert_eq!(group.interact(), None);
+
+        input.press_key(KeyCode::Left);
+        assert_eq!(group.interact(), None);
+        group.keyboard = true;
+        assert_eq!(group.interact(), Some(2));
+        input.press_key(KeyCode::Right);
+        assert_eq!(group.interact(), Some(0));
+        input.press_key(KeyCode::Down);
+        assert_eq!(group.interact(), None);
+
+        assert!(!group.select(3));
+        assert!(!group.select(0));
+        assert!(group.select(1));
+        assert_eq!(group.selected(), 1);
+    }
+}
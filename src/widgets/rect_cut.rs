@@ -0,0 +1,145 @@
+//! Quick layouts by cutting rects: start with the screen, cut off a toolbar from the top, a
+//! sidebar from the left, split the rest into columns, and give each piece to a widget with
+//! [`Widget::set_rect`] or [`place`]. For example:
+//!
+//! ```ignore
+//! let mut screen = Rect::new(0.0, 0.0, screen_width(), screen_height());
+//! let toolbar = cut_top(&mut screen, 40.0);
+//! let sidebar = cut_left(&mut screen, 200.0);
+//! let columns = split_columns(shrink(toolbar, vec2(4.0, 4.0)), 3, 4.0);
+//! for (button, column) in buttons.iter_mut().zip(columns) {
+//!     button.set_rect(column);
+//! }
+//! place(&mut title, sidebar, Horizontal::Center, Vertical::Top);
+//! ```
+//!
+//! Cuts never take more than what's left, so `rect` ends up empty instead of with a negative size.
+
+use crate::lazy::{add_contour, Pad};
+use crate::widgets::anchor::{Anchor, Horizontal, Vertical};
+use crate::widgets::text::Pixels;
+use crate::widgets::Widget;
+use macroquad::math::{vec2, Rect};
+
+/// Removes the left `pixels` of `rect` and returns them.
+pub fn cut_left(rect: &mut Rect, pixels: Pixels) -> Rect {
+    let w = pixels.clamp(0.0, rect.w);
+    let cut = Rect::new(rect.x, rect.y, w, rect.h);
+    rect.x += w;
+    rect.w -= w;
+    cut
+}
+
+/// Removes the right `pixels` of `rect` and returns them.
+pub fn cut_right(rect: &mut Rect, pixels: Pixels) -> Rect {
+    let w = pixels.clamp(0.0, rect.w);
+    rect.w -= w;
+    Rect::new(rect.x + rect.w, rect.y, w, rect.h)
+}
+
+/// Removes the top `pixels` of `rect` and returns them.
+pub fn cut_top(rect: &mut Rect, pixels: Pixels) -> Rect {
+    let h = pixels.clamp(0.0, rect.h);
+    let cut = Rect::new(rect.x, rect.y, rect.w, h);
+    rect.y += h;
+    rect.h -= h;
+    cut
+}
+
+/// Removes the bottom `pixels` of `rect` and returns them.
+pub fn cut_bottom(rect: &mut Rect, pixels: Pixels) -> Rect {
+    let h = pixels.clamp(0.0, rect.h);
+    rect.h -= h;
+    Rect::new(rect.x, rect.y + rect.h, rect.w, h)
+}
+
+/// `count` columns of the same width, with `gap` pixels between them.
+pub fn split_columns(rect: Rect, count: usize, gap: Pixels) -> Vec<Rect> {
+    let (w, step) = split(rect.w, count, gap);
+    (0..count)
+        .map(|i| Rect::new(rect.x + step * i as f32, rect.y, w, rect.h))
+        .collect()
+}
+
+/// `count` rows of the same height, with `gap` pixels between them.
+pub fn split_rows(rect: Rect, count: usize, gap: Pixels) -> Vec<Rect> {
+    let (h, step) = split(rect.h, count, gap);
+    (0..count)
+        .map(|i| Rect::new(rect.x, rect.y + step * i as f32, rect.w, h))
+        .collect()
+}
+
+/// Length of each piece and distance between the starts of consecutive pieces.
+fn split(length: Pixels, count: usize, gap: Pixels) -> (Pixels, Pixels) {
+    let gaps = gap * count.saturating_sub(1) as f32;
+    let piece = ((length - gaps) / count.max(1) as f32).max(0.0);
+    (piece, piece + gap)
+}
+
+/// `rect` without the `insets`, like a pad. See [`add_contour`] for what happens if the insets
+/// are bigger than the rect.
+pub fn shrink<P: Into<Pad>>(rect: Rect, insets: P) -> Rect {
+    add_contour(rect, -insets.into())
+}
+
+/// Moves `widget` inside `rect` at the given alignment, keeping its size, e.g. to center a
+/// [`crate::widgets::button::Button`] in a column. To make it fill the rect use
+/// [`Widget::set_rect`] instead.
+pub fn place<W: Widget + ?Sized>(
+    widget: &mut W,
+    rect: Rect,
+    horizontal: Horizontal,
+    vertical: Vertical,
+) {
+    widget.reanchor(Anchor::inside_concrete(
+        rect,
+        horizontal,
+        vertical,
+        vec2(0.0, 0.0),
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cuts() {
+        let mut rect = Rect::new(0.0, 0.0, 100.0, 50.0);
+        assert_eq!(cut_top(&mut rect, 10.0), Rect::new(0.0, 0.0, 100.0, 10.0));
+        assert_eq!(cut_left(&mut rect, 20.0), Rect::new(0.0, 10.0, 20.0, 40.0));
+        assert_eq!(
+            cut_right(&mut rect, 30.0),
+            Rect::new(70.0, 10.0, 30.0, 40.0)
+        );
+        assert_eq!(cut_bottom(&mut rect, 5.0), Rect::new(20.0, 45.0, 50.0, 5.0));
+        assert_eq!(rect, Rect::new(20.0, 10.0, 50.0, 35.0));
+        assert_eq!(cut_left(&mut rect, 80.0), Rect::new(20.0, 10.0, 50.0, 35.0));
+        assert_eq!(rect, Rect::new(70.0, 10.0, 0.0, 35.0));
+    }
+
+    #[test]
+    fn test_splits() {
+        let rect = Rect::new(10.0, 0.0, 100.0, 50.0);
+        assert_eq!(
+            split_columns(rect, 3, 5.0),
+            vec![
+                Rect::new(10.0, 0.0, 30.0, 50.0),
+                Rect::new(45.0, 0.0, 30.0, 50.0),
+                Rect::new(80.0, 0.0, 30.0, 50.0),
+            ]
+        );
+        assert_eq!(
+            split_rows(rect, 2, 0.0),
+            vec![
+                Rect::new(10.0, 0.0, 100.0, 25.0),
+                Rect::new(10.0, 25.0, 100.0, 25.0),
+            ]
+        );
+        assert_eq!(split_columns(rect, 0, 5.0), Vec::new());
+        assert_eq!(
+            shrink(rect, Pad::sides(1.0, 2.0, 3.0, 4.0)),
+            Rect::new(14.0, 1.0, 94.0, 46.0)
+        );
+    }
+}
@@ -1,6 +1,11 @@
+use crate::fonts;
+use crate::fonts::{FontId, DEFAULT_FONT};
+use crate::i18n;
+use crate::i18n::Localized;
+use crate::measure_cache;
 use crate::widgets::anchor::Anchor;
 use crate::widgets::{StateStyle, Style, Widget};
-use crate::PositionInPixels2d;
+use crate::{PositionInPixels2d, SizeInPixels2d};
 use macroquad::prelude::{Color, Font, Rect, TextDimensions, Vec2};
 use macroquad::text::TextParams;
 use std::ops::AddAssign;
@@ -47,13 +52,16 @@ pub struct TextRect {
     pub text: String,
     pub rect: Rect,
     pub font_size: f32,
-    pub font: Option<Font>,
+    pub font: FontId,
     /// if the pad is asymmetric, this is the left/top pad
     pub pad: Vec2,
     pub offset_y: f32,
     pub text_width: f32,
     pub text_height: f32,
     pub reference_height: f32,
+    /// if Some, `text` is its translation at the time of `language_revision`
+    pub localized: Option<Localized>,
+    pub language_revision: u64,
 }
 
 impl Widget for TextRect {
@@ -66,28 +74,38 @@ impl Widget for TextRect {
 }
 
 impl TextRect {
+    /// Measured with [`measure_cache::measure_text_cached`], as this is called every frame by
+    /// [`draw_text_lines`].
     pub fn new(text: &str, position_pixels: Anchor, font_size: f32) -> Self {
-        Self::new_generic(
-            text,
-            position_pixels,
-            font_size,
-            None,
-            macroquad::prelude::measure_text,
-        )
+        Self::new_measured(text, position_pixels, font_size, DEFAULT_FONT, |text| {
+            measure_cache::measure_text_cached(text, DEFAULT_FONT, font_size as u16, 1.0)
+        })
     }
 
     pub fn new_generic(
         text: &str,
         position_pixels: Anchor,
         font_size: f32,
-        font: Option<&Font>,
+        font: FontId,
         measure_text: MeasureText,
+    ) -> Self {
+        Self::new_measured(text, position_pixels, font_size, font, |text| {
+            measure_cache::measure_text(text, font, font_size as u16, 1.0, measure_text)
+        })
+    }
+
+    fn new_measured(
+        text: &str,
+        position_pixels: Anchor,
+        font_size: f32,
+        font: FontId,
+        measure: impl Fn(&str) -> TextDimensions,
     ) -> Self {
         // font_size doesn't seem to be in pixels across fonts
-        let reference_size = measure_text("Odp", font, font_size as u16, 1.0);
+        let reference_size = measure("Odp");
         let reference_height = reference_size.height;
         let pad = Vec2::new(reference_height, reference_height * 0.75);
-        let text_dimensions = measure_text(text, font, font_size as u16, 1.0);
+        let text_dimensions = measure(text);
 
         let size = Vec2::new(
             (text_dimensions.width + pad.x * 2.0).round(),
@@ -100,19 +118,111 @@ impl TextRect {
             text: text.to_string(),
             rect,
             font_size,
-            font: if let Some(font) = font {
-                Some(font.clone())
-            } else {
-                None
-            },
+            font,
             pad,
             offset_y: text_dimensions.offset_y,
             text_width: text_dimensions.width,
             text_height: text_dimensions.height,
             reference_height,
+            localized: None,
+            language_revision: 0,
         }
     }
 
+    /// The font size is chosen so that the text fits in `size`, see [`fit_text`]. The text is
+    /// centered in the rect.
+    pub fn new_fit(
+        text: &str,
+        position_pixels: Anchor,
+        size: SizeInPixels2d,
+        auto_fit: AutoFit,
+    ) -> Self {
+        Self::new_fit_generic(
+            text,
+            position_pixels,
+            size,
+            auto_fit,
+            DEFAULT_FONT,
+            macroquad::prelude::measure_text,
+        )
+    }
+    pub fn new_fit_generic(
+        text: &str,
+        position_pixels: Anchor,
+        size: SizeInPixels2d,
+        auto_fit: AutoFit,
+        font: FontId,
+        measure_text: MeasureText,
+    ) -> Self {
+        let fitted = fit_text(text, size, auto_fit, font, measure_text);
+        let mut text_rect = Self::new_generic(
+            &fitted.text,
+            position_pixels,
+            fitted.font_size,
+            font,
+            measure_text,
+        );
+        text_rect.pad = Vec2::new(
+            ((size.x - text_rect.text_width) * 0.5).max(0.0),
+            ((size.y - text_rect.reference_height) * 0.5).max(0.0),
+        );
+        text_rect.rect = position_pixels.get_rect(size);
+        text_rect
+    }
+
+    pub fn new_localized(localized: Localized, position_pixels: Anchor, font_size: f32) -> Self {
+        Self::new_localized_generic(
+            localized,
+            position_pixels,
+            font_size,
+            DEFAULT_FONT,
+            macroquad::prelude::measure_text,
+        )
+    }
+    pub fn new_localized_generic(
+        localized: Localized,
+        position_pixels: Anchor,
+        font_size: f32,
+        font: FontId,
+        measure_text: MeasureText,
+    ) -> Self {
+        let language_revision = i18n::revision();
+        let text = localized.resolve();
+        let mut text_rect =
+            Self::new_generic(&text, position_pixels, font_size, font, measure_text);
+        text_rect.localized = Some(localized);
+        text_rect.language_revision = language_revision;
+        text_rect
+    }
+
+    pub fn needs_relocalize(&self) -> bool {
+        self.localized.is_some() && self.language_revision != i18n::revision()
+    }
+    /// If the language changed, translates and measures the text again, and places it at
+    /// `position_pixels`. Returns whether anything changed.
+    pub fn relocalize(&mut self, position_pixels: Anchor) -> bool {
+        self.relocalize_generic(position_pixels, macroquad::prelude::measure_text)
+    }
+    pub fn relocalize_generic(
+        &mut self,
+        position_pixels: Anchor,
+        measure_text: MeasureText,
+    ) -> bool {
+        if !self.needs_relocalize() {
+            return false;
+        }
+        if let Some(localized) = self.localized.take() {
+            *self = Self::new_localized_generic(
+                localized,
+                position_pixels,
+                self.font_size,
+                self.font,
+                measure_text,
+            );
+        }
+        true
+    }
+
     pub fn render_text(&self, color: Color) {
         let mut style = Style::default();
         style.at_rest.text_color = color;
@@ -142,41 +252,169 @@ pub fn draw_text_rect_generic(text_rect: &TextRect, style: &StateStyle, draw_tex
         (text_rect.rect.y + text_rect.pad.y + approx_height_from_baseline_to_top).round(),
         text_rect.font_size,
         &style,
-        text_rect.font.as_ref(),
+        text_rect.font,
     );
 }
 
 pub type DrawText =
-    fn(text: &str, x: f32, y: f32, font_size: f32, style: &StateStyle, font: Option<&Font>);
+    fn(text: &str, x: f32, y: f32, font_size: f32, style: &StateStyle, font: FontId);
 
 /// Here the x and y are the baseline of the text as macroquad expects.
-pub fn draw_text(
-    text: &str,
-    x: f32,
-    y: f32,
-    font_size: f32,
-    style: &StateStyle,
-    font: Option<&Font>,
-) {
+pub fn draw_text(text: &str, x: f32, y: f32, font_size: f32, style: &StateStyle, font: FontId) {
     let params = TextParams {
-        font,
         font_size: font_size as u16,
         color: style.text_color,
         ..TextParams::default()
     };
-    macroquad::text::draw_text_ex(text, x, y, params);
+    if let Some(shadow) = style.text_shadow {
+        let shadow_params = TextParams {
+            color: shadow.color,
+            ..params.clone()
+        };
+        let Vec2 { x: dx, y: dy } = shadow.offset;
+        fonts::draw_text_ex(text, x + dx, y + dy, font, shadow_params);
+    }
+    if let Some(outline) = style.text_outline {
+        let outline_params = TextParams {
+            color: outline.color,
+            ..params.clone()
+        };
+        for Vec2 { x: dx, y: dy } in outline_offsets(outline.thickness) {
+            fonts::draw_text_ex(text, x + dx, y + dy, font, outline_params.clone());
+        }
+    }
+    fonts::draw_text_ex(text, x, y, font, params);
+}
+
+/// The outline is done by drawing the text several times around its position, in rings of
+/// 8 directions, 1 pixel apart.
+pub fn outline_offsets(thickness: f32) -> Vec<Vec2> {
+    let mut offsets = Vec::new();
+    let rings = thickness.ceil() as i32;
+    for ring in 1..=rings {
+        let radius = thickness.min(ring as f32);
+        for (dx, dy) in [
+            (-1.0, -1.0),
+            (0.0, -1.0),
+            (1.0, -1.0),
+            (-1.0, 0.0),
+            (1.0, 0.0),
+            (-1.0, 1.0),
+            (0.0, 1.0),
+            (1.0, 1.0),
+        ] {
+            offsets.push(Vec2::new(dx, dy) * radius);
+        }
+    }
+    offsets
 }
 pub fn draw_text_v(
     text: &str,
     position: PositionInPixels2d,
     font_size: f32,
     style: &StateStyle,
-    font: Option<&Font>,
+    font: FontId,
 ) {
     let Vec2 { x, y } = position;
     draw_text(text, x, y, font_size, style, font)
 }
 
+/// Range of font sizes to try when the text has to fit in a fixed size.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AutoFit {
+    pub min_font_size: f32,
+    pub max_font_size: f32,
+}
+
+impl AutoFit {
+    pub fn new(min_font_size: f32, max_font_size: f32) -> Self {
+        Self {
+            min_font_size,
+            max_font_size,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FittedText {
+    /// might be shortened with an ellipsis
+    pub text: String,
+    pub font_size: f32,
+}
+
+/// Picks the largest font size in the range whose text fits in `available`. If not even the
+/// minimum font size fits, the text is cut with "..." so that it fits with the minimum font size.
+pub fn fit_text(
+    text: &str,
+    available: SizeInPixels2d,
+    auto_fit: AutoFit,
+    font: FontId,
+    measure_text: MeasureText,
+) -> FittedText {
+    let fits = |font_size: u16| {
+        let reference = measure_cache::measure_text("Odp", font, font_size, 1.0, measure_text);
+        let dimensions = measure_cache::measure_text(text, font, font_size, 1.0, measure_text);
+        dimensions.width <= available.x && reference.height <= available.y
+    };
+    let mut smallest = auto_fit.min_font_size.ceil() as u16;
+    let mut biggest = (auto_fit.max_font_size.floor() as u16).max(smallest);
+    if !fits(smallest) {
+        return FittedText {
+            text: ellipsize(text, available.x, font, smallest as f32, measure_text),
+            font_size: smallest as f32,
+        };
+    }
+    while smallest < biggest {
+        let middle = (smallest + biggest).div_ceil(2);
+        if fits(middle) {
+            smallest = middle;
+        } else {
+            biggest = middle - 1;
+        }
+    }
+    FittedText {
+        text: text.to_string(),
+        font_size: smallest as f32,
+    }
+}
+
+/// Returns the longest start of the text that, followed by "...", fits in `max_width`.
+/// If the whole text fits, it's returned untouched, and if not even "..." fits, returns "".
+pub fn ellipsize(
+    text: &str,
+    max_width: Pixels,
+    font: FontId,
+    font_size: f32,
+    measure_text: MeasureText,
+) -> String {
+    let width = |text: &str| {
+        measure_cache::measure_text(text, font, font_size as u16, 1.0, measure_text).width
+    };
+    if width(text) <= max_width {
+        return text.to_string();
+    }
+    let boundaries = text
+        .char_indices()
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+    let with_ellipsis = |chars: usize| format!("{}...", &text[..boundaries[chars]]);
+    if width(&with_ellipsis(0)) > max_width {
+        return String::new();
+    }
+    // the text doesn't fit, so the answer is less than the whole text
+    let mut fitting_chars = 0;
+    let mut not_fitting_chars = boundaries.len();
+    while fitting_chars + 1 < not_fitting_chars {
+        let middle = (fitting_chars + not_fitting_chars) / 2;
+        if width(&with_ellipsis(middle)) <= max_width {
+            fitting_chars = middle;
+        } else {
+            not_fitting_chars = middle;
+        }
+    }
+    with_ellipsis(fitting_chars)
+}
+
 /// A nice combo is:
 /// ```no_run
 /// use macroquad::prelude::BLACK;
@@ -332,6 +570,77 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::testing::mock_measure;
+
+    #[test]
+    fn test_fit_text_biggest_font() {
+        let auto_fit = AutoFit::new(8.0, 20.0);
+        let fitted = fit_text("abcd", Vec2::new(100.0, 15.0), auto_fit, 0, mock_measure);
+        assert_eq!(fitted.text, "abcd");
+        assert_eq!(fitted.font_size, 15.0);
+
+        let fitted = fit_text("abcd", Vec2::new(30.0, 30.0), auto_fit, 0, mock_measure);
+        assert_eq!(fitted.font_size, 10.0);
+
+        let fitted = fit_text("abcd", Vec2::new(300.0, 300.0), auto_fit, 0, mock_measure);
+        assert_eq!(fitted.font_size, 20.0);
+    }
+
+    #[test]
+    fn test_fit_text_ellipsis() {
+        let auto_fit = AutoFit::new(10.0, 20.0);
+        let fitted = fit_text(
+            "abcdefghij",
+            Vec2::new(40.0, 30.0),
+            auto_fit,
+            0,
+            mock_measure,
+        );
+        assert_eq!(fitted.text, "ab...");
+        assert_eq!(fitted.font_size, 10.0);
+
+        let fitted = fit_text(
+            "abcdefghij",
+            Vec2::new(10.0, 30.0),
+            auto_fit,
+            0,
+            mock_measure,
+        );
+        assert_eq!(fitted.text, "");
+    }
+
+    #[test]
+    fn test_relocalize() {
+        i18n::load_language_str("en", "exit = Exit").unwrap();
+        i18n::load_language_str("es", "exit = Salir").unwrap();
+        i18n::set_language("en");
+        let anchor = Anchor::top_left(10.0, 0.0);
+        let mut text_rect = TextRect::new_localized_generic(
+            "exit".into(),
+            anchor,
+            20.0,
+            DEFAULT_FONT,
+            mock_measure,
+        );
+        assert_eq!(text_rect.text, "Exit");
+        assert_eq!(text_rect.rect, Rect::new(10.0, 0.0, 100.0, 50.0));
+        assert!(!text_rect.relocalize_generic(anchor, mock_measure));
+
+        i18n::set_language("es");
+        assert!(text_rect.relocalize_generic(anchor, mock_measure));
+        assert_eq!(text_rect.text, "Salir");
+        assert_eq!(text_rect.rect, Rect::new(10.0, 0.0, 115.0, 50.0));
+        assert!(!text_rect.relocalize_generic(anchor, mock_measure));
+    }
+
+    #[test]
+    fn test_outline_offsets() {
+        assert_eq!(outline_offsets(0.0).len(), 0);
+        let offsets = outline_offsets(1.5);
+        assert_eq!(offsets.len(), 16);
+        assert_eq!(offsets[0], Vec2::new(-1.0, -1.0));
+        assert_eq!(offsets[15], Vec2::new(1.5, 1.5));
+    }
 
     #[test]
     fn test_wrap_text_basic() {
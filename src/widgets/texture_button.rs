@@ -3,6 +3,7 @@ use macroquad::prelude::{
     DrawTextureParams, MouseButton, Rect, Texture2D, Vec2, WHITE,
 };
 
+use crate::clip;
 use crate::widgets::anchor::Anchor;
 use crate::widgets::{Interaction, Widget};
 
@@ -37,7 +38,7 @@ impl TextureButton {
     }
 
     pub fn interact(&mut self) -> Interaction {
-        self.interaction = if self.rect.contains(Vec2::from(mouse_position())) {
+        self.interaction = if clip::clip_rect(self.rect).contains(Vec2::from(mouse_position())) {
             if is_mouse_button_down(MouseButton::Left) {
                 Interaction::Pressing
             } else if is_mouse_button_released(MouseButton::Left) {